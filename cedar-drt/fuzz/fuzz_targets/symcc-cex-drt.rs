@@ -18,11 +18,13 @@
 use cedar_drt::logger::initialize_log;
 use cedar_drt_inner::{
     fuzz_target,
-    symcc::{CexError, RUNTIME, TwoPolicyFuzzTargetInput, get_cex, get_solver, reproduce},
+    symcc::{
+        CexError, RUNTIME, TwoPolicyFuzzTargetInput, get_cex, get_solver, minimize_cex, reproduce,
+    },
 };
 use cedar_lean_ffi::CedarLeanFfi;
 use cedar_policy::Decision;
-use cedar_policy_symcc::CompiledPolicySet;
+use cedar_policy_symcc::{CompiledPolicySet, Env};
 
 // Fuzz target checking that the Rust and Lean implementations produce the same
 // counterexamples for SAT results (in this case, for Implies queries on
@@ -68,9 +70,18 @@ fuzz_target!(|input: TwoPolicyFuzzTargetInput<32>| {
                                             &req_env,
                                             &raw_model,
                                         )
-                                        .unwrap()
-                                        .try_into()
                                         .unwrap();
+                                    // the minimized counterexample must still be a
+                                    // counterexample to Implies
+                                    let min_cex = minimize_cex(
+                                        lean_cex.clone(),
+                                        &schema,
+                                        &[&policyset1, &policyset2],
+                                    );
+                                    let min_cex: Env = min_cex.try_into().unwrap();
+                                    assert_eq!(reproduce(&min_cex, &policyset1), Decision::Allow);
+                                    assert_eq!(reproduce(&min_cex, &policyset2), Decision::Deny);
+                                    let lean_cex: Env = lean_cex.try_into().unwrap();
                                     assert_eq!(rust_cex, lean_cex);
                                 }
                                 None => {
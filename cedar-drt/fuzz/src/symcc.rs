@@ -575,6 +575,31 @@ pub fn reproduce(env: &Env, policies: &PolicySet) -> cedar_policy::Decision {
         .decision()
 }
 
+/// Shrink the Lean counterexample `cex` (see `cedar_lean_ffi::Env::minimize`), keeping each removal
+/// only if the result still conforms to `schema` and `reproduce()` still gives the same `Decision`
+/// on each of `policysets` as it does on `cex`
+pub fn minimize_cex(
+    cex: cedar_lean_ffi::Env,
+    schema: &Schema,
+    policysets: &[&PolicySet],
+) -> cedar_lean_ffi::Env {
+    let decisions = |cex: &cedar_lean_ffi::Env| -> Option<Vec<cedar_policy::Decision>> {
+        let env: Env = cex.clone().try_into().ok()?;
+        Some(
+            policysets
+                .iter()
+                .map(|policies| reproduce(&env, policies))
+                .collect(),
+        )
+    };
+    let Some(expected) = decisions(&cex) else {
+        return cex;
+    };
+    cex.minimize(Some(schema), |candidate| {
+        decisions(candidate).as_ref() == Some(&expected)
+    })
+}
+
 pub trait ValidationTask: Sync {
     type RawInput: Send + Sync + Display;
     type CompiledInput: Send;
@@ -14,12 +14,12 @@
  * limitations under the License.
  */
 
-use cedar_policy::{AuthorizationError, Policy};
-use cedar_policy_core::entities::TypeAndId;
+use cedar_json_test::{json_request, passes_validation, write_test_case};
+use cedar_policy::Policy;
 
 use cedar_testing::cedar_test_impl::RustEngine;
-use cedar_testing::integration_testing::{JsonRequest, JsonTest, perform_integration_test};
-use std::{io::Write, path::Path, str::FromStr};
+use cedar_testing::integration_testing::{JsonRequest, perform_integration_test};
+use std::{path::Path, str::FromStr};
 
 /// Dump testcase to a directory.
 ///
@@ -43,91 +43,31 @@ pub fn dump(
         return Ok(());
     }
 
-    let dirname = dirname.as_ref();
-    std::fs::create_dir_all(dirname)?;
-
-    let schema_filename = dirname.join(format!("{testcasename}.cedarschema"));
-    let policies_filename = dirname.join(format!("{testcasename}.cedar"));
-    let entities_filename = dirname.join(format!("{testcasename}.entities.json"));
-    let testcase_filename = dirname.join(format!("{testcasename}.json"));
-
-    let mut schema_file = std::fs::OpenOptions::new()
-        .create(true)
-        .write(true)
-        .append(false)
-        .truncate(true)
-        .open(&schema_filename)?;
     let schema_text = schema.to_cedarschema().unwrap();
-    writeln!(schema_file, "{schema_text}")?;
-
-    let mut policies_file = std::fs::OpenOptions::new()
-        .create(true)
-        .write(true)
-        .append(false)
-        .truncate(true)
-        .open(&policies_filename)?;
     let static_policies: Vec<_> = policies
         .as_ref()
         .static_policies()
         .map(ToString::to_string)
         .collect();
     let policy_text = static_policies.join("\n");
-    writeln!(policies_file, "{policy_text}")?;
-
-    let entities_file = std::fs::OpenOptions::new()
-        .create(true)
-        .write(true)
-        .append(false)
-        .truncate(true)
-        .open(&entities_filename)?;
-    entities.write_to_json(entities_file).unwrap();
 
     let requests: Vec<JsonRequest> = requests
         .into_iter()
         .enumerate()
-        .map(|(i, (q, a))| JsonRequest {
-            description: format!("Request {i}"),
-            principal: dump_request_var(q.principal().unwrap()),
-            action: dump_request_var(q.action().unwrap()),
-            resource: dump_request_var(q.resource().unwrap()),
-            context: dump_context(
-                q.context()
-                    .expect("`dump` does not support requests missing context")
-                    .clone(),
-            ),
-            validate_request: true,
-            decision: a.decision(),
-            reason: a.clone().diagnostics().reason().cloned().collect(),
-            errors: a
-                .diagnostics()
-                .errors()
-                .map(|e| match e {
-                    AuthorizationError::PolicyEvaluationError(e) => e.policy_id(),
-                })
-                .cloned()
-                .collect(),
-        })
+        .map(|(i, (q, a))| json_request(format!("Request {i}"), &q, &a))
         .collect();
 
     let should_validate = passes_validation(schema.clone(), policies);
 
-    let testcase = JsonTest {
-        schema: schema_filename.display().to_string(),
-        policies: policies_filename.display().to_string(),
-        policy_format: Default::default(),
-        entities: entities_filename.display().to_string(),
-        schema_format: Default::default(),
+    write_test_case(
+        dirname.as_ref(),
+        testcasename,
+        &schema_text,
+        &policy_text,
+        entities,
         should_validate,
-        requests: requests.clone(),
-    };
-
-    let testcase_file = std::fs::OpenOptions::new()
-        .create(true)
-        .write(true)
-        .append(false)
-        .truncate(true)
-        .open(testcase_filename)?;
-    serde_json::to_writer_pretty(testcase_file, &testcase)?;
+        requests.clone(),
+    )?;
 
     // The generated test case should successfully run
     check_test(
@@ -185,33 +125,3 @@ fn well_formed(policies: &cedar_policy::PolicySet) -> bool {
         .map(ToString::to_string)
         .all(|p| Policy::from_str(&p).is_ok())
 }
-
-/// Check whether a policy set passes validation
-fn passes_validation(
-    schema: cedar_policy::SchemaFragment,
-    policies: &cedar_policy::PolicySet,
-) -> bool {
-    if let Ok(schema) = schema.try_into() {
-        let validator = cedar_policy::Validator::new(schema);
-        validator
-            .validate(policies, cedar_policy::ValidationMode::default())
-            .validation_passed()
-    } else {
-        false
-    }
-}
-
-/// Dump the entity uid to a json value
-fn dump_request_var(var: &cedar_policy::EntityUid) -> serde_json::Value {
-    let tyid = TypeAndId::from(var.as_ref());
-    serde_json::to_value(tyid).expect("failed to serialize euid")
-}
-
-/// Dump the context to a "natural" json value
-fn dump_context(context: cedar_policy::Context) -> serde_json::Value {
-    let context = context
-        .into_iter()
-        .map(|(k, pval)| (k, pval.as_ref().to_natural_json().unwrap()))
-        .collect::<indexmap::IndexMap<_, _>>();
-    serde_json::to_value(context).expect("failed to serialize context")
-}
@@ -0,0 +1,123 @@
+/*
+ * Copyright Cedar Contributors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Writing integration test cases in the `JsonTest` layout of the `cedar-drt` corpus, shared by
+//! `cedar-drt` (for fuzzer-generated inputs) and `cedar-lean-cli` (for counterexamples).
+
+#![forbid(unsafe_code)]
+
+use cedar_policy::{
+    AuthorizationError, Context, Entities, EntityUid, PolicySet, Request, Response, SchemaFragment,
+    ValidationMode, Validator,
+};
+use cedar_policy_core::entities::TypeAndId;
+use cedar_testing::integration_testing::{JsonRequest, JsonTest};
+use std::path::Path;
+
+/// Write a test case to a directory: a `.cedarschema`, `.cedar`, `.entities.json` and a `.json`
+/// `JsonTest` file, all named after `testcasename`.
+///
+/// `dirname`: directory in which to write the test case. Will be created if it doesn't exist.
+pub fn write_test_case(
+    dirname: &Path,
+    testcasename: &str,
+    schema_text: &str,
+    policy_text: &str,
+    entities: &Entities,
+    should_validate: bool,
+    requests: Vec<JsonRequest>,
+) -> std::io::Result<()> {
+    std::fs::create_dir_all(dirname)?;
+
+    let schema_filename = dirname.join(format!("{testcasename}.cedarschema"));
+    let policies_filename = dirname.join(format!("{testcasename}.cedar"));
+    let entities_filename = dirname.join(format!("{testcasename}.entities.json"));
+    let testcase_filename = dirname.join(format!("{testcasename}.json"));
+
+    std::fs::write(&schema_filename, format!("{schema_text}\n"))?;
+    std::fs::write(&policies_filename, format!("{policy_text}\n"))?;
+    let entities_file = std::fs::File::create(&entities_filename)?;
+    entities
+        .write_to_json(entities_file)
+        .map_err(std::io::Error::other)?;
+
+    let testcase = JsonTest {
+        schema: schema_filename.display().to_string(),
+        policies: policies_filename.display().to_string(),
+        policy_format: Default::default(),
+        entities: entities_filename.display().to_string(),
+        schema_format: Default::default(),
+        should_validate,
+        requests,
+    };
+    let testcase_file = std::fs::File::create(testcase_filename)?;
+    serde_json::to_writer_pretty(testcase_file, &testcase)?;
+    Ok(())
+}
+
+/// The `JsonRequest` expecting `response` for `request`, which must have all its components
+pub fn json_request(description: String, request: &Request, response: &Response) -> JsonRequest {
+    JsonRequest {
+        description,
+        principal: dump_request_var(request.principal().unwrap()),
+        action: dump_request_var(request.action().unwrap()),
+        resource: dump_request_var(request.resource().unwrap()),
+        context: dump_context(
+            request
+                .context()
+                .expect("test cases do not support requests missing context")
+                .clone(),
+        ),
+        validate_request: true,
+        decision: response.decision(),
+        reason: response.diagnostics().reason().cloned().collect(),
+        errors: response
+            .diagnostics()
+            .errors()
+            .map(|e| match e {
+                AuthorizationError::PolicyEvaluationError(e) => e.policy_id(),
+            })
+            .cloned()
+            .collect(),
+    }
+}
+
+/// Check whether a policy set passes validation
+pub fn passes_validation(schema: SchemaFragment, policies: &PolicySet) -> bool {
+    if let Ok(schema) = schema.try_into() {
+        let validator = Validator::new(schema);
+        validator
+            .validate(policies, ValidationMode::default())
+            .validation_passed()
+    } else {
+        false
+    }
+}
+
+/// Dump the entity uid to a json value
+fn dump_request_var(var: &EntityUid) -> serde_json::Value {
+    let tyid = TypeAndId::from(var.as_ref());
+    serde_json::to_value(tyid).expect("failed to serialize euid")
+}
+
+/// Dump the context to a "natural" json value
+fn dump_context(context: Context) -> serde_json::Value {
+    let context = context
+        .into_iter()
+        .map(|(k, pval)| (k, pval.as_ref().to_natural_json().unwrap()))
+        .collect::<indexmap::IndexMap<_, _>>();
+    serde_json::to_value(context).expect("failed to serialize context")
+}
@@ -13,41 +13,94 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
-use crate::util::{AnalyzePolicyFindingsSer, OpenRequestEnv};
-use crate::{err::ExecError, util::RequestEnvSer};
-use cedar_lean_ffi::{CedarLeanFfi, FfiError, LeanSchema};
-use cedar_policy::{Effect, Policy, PolicyId, PolicySet, RequestEnv, RestrictedExpression, Schema};
+use crate::sarif::SarifLog;
+use crate::solver::is_unknown;
+use crate::util::{
+    AnalyzePolicyFindingsSer, OpenRequestEnv, TimedOutputSer, TimedStep, Timings, TimingsSer,
+    is_json_file, parse_policyset_spans, singleton_policyset, split_timed,
+};
+use crate::{
+    err::{ContentType, ExecError},
+    util::RequestEnvSer,
+};
+use cedar_lean_ffi::{CedarLeanFfi, Env, FfiError, LeanSchema, TimedResult};
+use cedar_policy::{
+    Authorizer, Effect, Policy, PolicyId, PolicySet, RequestEnv, RestrictedExpression, Schema,
+};
 use itertools::Itertools;
 use nonempty::NonEmpty;
 use prettytable::{Attr, Cell, Row, Table};
 use serde::Serialize;
 use std::fmt::Write;
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     iter::zip,
+    num::NonZeroUsize,
+    path::PathBuf,
+    sync::atomic::{AtomicU64, AtomicUsize, Ordering},
+    time::Duration,
 };
 
 pub struct Analyzer<'a> {
     /// `CedarLeanFfi`, initialized once and used for many calls
-    lean_ffi: CedarLeanFfi,
+    lean_ffi: &'a CedarLeanFfi,
     /// Rust schema object
     schema: &'a Schema,
     /// Lean schema object, parsed/deserialized once and used for many calls
     lean_schema: LeanSchema,
     /// Whether to output in JSON
     json_output: bool,
+    /// Number of worker threads used by `analyze_policyset`
+    jobs: NonZeroUsize,
+    /// If set, `analyze_policyset` outputs SARIF, locating policies in this PolicySet file
+    sarif_output: Option<PathBuf>,
+    /// Whether `analyze_policyset` also reports policies that may error
+    check_errors: bool,
+    /// Whether `compare_policysets` also reports which policies cause the differences
+    attribute_differences: bool,
+    /// Whether `analyze_policyset` and `compare_policysets` also report the time spent in Lean
+    timings: bool,
+    /// The time spent in Lean by this `Analyzer` so far, in nanoseconds
+    lean_nanos: AtomicU64,
+    /// The number of solver queries this `Analyzer` could not decide so far
+    unknown_queries: AtomicUsize,
 }
 
 impl<'a> Analyzer<'a> {
-    pub fn new(schema: &'a Schema, json_output: bool) -> Result<Self, FfiError> {
-        let lean_ffi = CedarLeanFfi::new();
+    pub fn new(
+        lean_ffi: &'a CedarLeanFfi,
+        schema: &'a Schema,
+        json_output: bool,
+    ) -> Result<Self, FfiError> {
         let lean_schema = lean_ffi.load_lean_schema_object(schema)?;
-        Ok(Self {
+        Ok(Self::with_lean_schema(
             lean_ffi,
             schema,
             lean_schema,
             json_output,
-        })
+        ))
+    }
+
+    /// Construct an `Analyzer` from a Lean schema object that was already loaded for `schema`
+    pub(crate) fn with_lean_schema(
+        lean_ffi: &'a CedarLeanFfi,
+        schema: &'a Schema,
+        lean_schema: LeanSchema,
+        json_output: bool,
+    ) -> Self {
+        Self {
+            lean_ffi,
+            schema,
+            lean_schema,
+            json_output,
+            jobs: NonZeroUsize::MIN,
+            sarif_output: None,
+            check_errors: false,
+            attribute_differences: false,
+            timings: false,
+            lean_nanos: AtomicU64::new(0),
+            unknown_queries: AtomicUsize::new(0),
+        }
     }
 
     /// Change the `json_output` setting without reconstructing an entire new `Analyzer`
@@ -55,16 +108,210 @@ impl<'a> Analyzer<'a> {
         self.json_output = json_output;
     }
 
-    /// Analyze a Cedar `PolicySet` with respect to the `Analyzer`'s `Schema` and print the findings
-    pub fn analyze_policyset(&self, policy_set: PolicySet) -> Result<(), ExecError> {
-        let mut policy_vacuity_results = HashMap::new();
+    /// Change the number of worker threads used by `analyze_policyset`
+    pub fn set_jobs(&mut self, jobs: NonZeroUsize) {
+        self.jobs = jobs;
+    }
+
+    /// Make `analyze_policyset` output SARIF for the PolicySet in `policyset_file` (or not, if `None`)
+    pub fn set_sarif_output(&mut self, policyset_file: Option<PathBuf>) {
+        self.sarif_output = policyset_file;
+    }
+
+    /// Change whether `analyze_policyset` reports policies that may error
+    pub fn set_check_errors(&mut self, check_errors: bool) {
+        self.check_errors = check_errors;
+    }
+
+    /// Change whether `compare_policysets` reports which policies cause the differences
+    pub fn set_attribute_differences(&mut self, attribute_differences: bool) {
+        self.attribute_differences = attribute_differences;
+    }
+
+    /// Change whether `analyze_policyset`, `minimize_policyset` and `compare_policysets` report the
+    /// time spent in Lean
+    pub fn set_timings(&mut self, timings: bool) {
+        self.timings = timings;
+    }
+
+    /// Record the time spent in a `*_timed` Lean call, and return its result
+    fn timed<T>(&self, result: Result<TimedResult<T>, FfiError>) -> Result<T, FfiError> {
+        let (result, duration) = split_timed(result?);
+        self.lean_nanos
+            .fetch_add(duration.as_nanos() as u64, Ordering::Relaxed);
+        Ok(result)
+    }
+
+    /// Like `timed`, but return `None` (counting the query as unknown) if the solver could not
+    /// decide the query
+    fn decided<T>(&self, result: Result<TimedResult<T>, FfiError>) -> Result<Option<T>, FfiError> {
+        match self.timed(result) {
+            Err(err) if is_unknown(&err) => {
+                self.unknown_queries.fetch_add(1, Ordering::Relaxed);
+                Ok(None)
+            }
+            result => result.map(Some),
+        }
+    }
+
+    /// Warn on stderr if the solver could not decide some queries, as the findings depending on
+    /// them are reported as unknown
+    fn warn_unknown_queries(&self) {
+        let unknown_queries = self.unknown_queries.load(Ordering::Relaxed);
+        if unknown_queries > 0 {
+            eprintln!(
+                "Warning: the solver could not decide {unknown_queries} queries (e.g., they timed out); findings depending on them are reported as unknown"
+            );
+        }
+    }
+
+    /// Run `f`, also returning the time this `Analyzer` spent in Lean while running it
+    fn with_lean_time<R>(
+        &self,
+        f: impl FnOnce() -> Result<R, ExecError>,
+    ) -> Result<(R, Duration), ExecError> {
+        let start = self.lean_nanos.load(Ordering::Relaxed);
+        let result = f()?;
+        let end = self.lean_nanos.load(Ordering::Relaxed);
+        Ok((result, Duration::from_nanos(end - start)))
+    }
+
+    /// Apply `f` to every item, returning the results in the order of `items`.
+    ///
+    /// With more than one job, the items are distributed over worker threads,
+    /// each with its own `Analyzer` (and thus its own Lean runtime and Lean schema object).
+    fn map_jobs<T, R>(
+        &self,
+        items: &[T],
+        f: impl Fn(&Analyzer<'_>, &T) -> Result<R, ExecError> + Sync,
+    ) -> Result<Vec<R>, ExecError>
+    where
+        T: Sync,
+        R: Send,
+    {
+        let jobs = self.jobs.get().min(items.len());
+        if jobs <= 1 {
+            return items.iter().map(|item| f(self, item)).collect();
+        }
+        let next = AtomicUsize::new(0);
+        // Each worker initializes its thread for Lean; the thread is finalized when `lean_ffi`
+        // is dropped, after the `analyzer` (and its Lean schema object) declared below it
+        let worker = || -> Result<Vec<(usize, R)>, ExecError> {
+            let lean_ffi = CedarLeanFfi::new();
+            let analyzer = Analyzer::new(&lean_ffi, self.schema, self.json_output)?;
+            let mut results = Vec::new();
+            loop {
+                let ind = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(ind) else {
+                    self.unknown_queries.fetch_add(
+                        analyzer.unknown_queries.load(Ordering::Relaxed),
+                        Ordering::Relaxed,
+                    );
+                    return Ok(results);
+                };
+                results.push((ind, f(&analyzer, item)?));
+            }
+        };
+        let worker_results: Vec<Result<Vec<(usize, R)>, ExecError>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..jobs).map(|_| scope.spawn(worker)).collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("analysis worker thread panicked"))
+                .collect()
+        });
+        let mut results = Vec::with_capacity(items.len());
+        for worker_result in worker_results {
+            results.extend(worker_result?);
+        }
+        results.sort_by_key(|(ind, _)| *ind);
+        Ok(results.into_iter().map(|(_, result)| result).collect())
+    }
+
+    /// Analyze a Cedar `PolicySet` with respect to the `Analyzer`'s `Schema` and print the findings,
+    /// restricted to the request environments described by `request_env`
+    pub fn analyze_policyset(
+        &self,
+        policy_set: PolicySet,
+        request_env: &OpenRequestEnv,
+    ) -> Result<(), ExecError> {
+        let req_envs = request_env.to_request_envs(self.schema)?;
+        let mut timings = Timings::default();
+        let findings = self.policyset_findings(&policy_set, req_envs, &mut timings)?;
+        if let Some(policyset_file) = &self.sarif_output {
+            findings.print_sarif(policyset_file)?;
+        } else if self.json_output && self.timings {
+            let timed = TimedOutputSer {
+                results: AnalyzePolicyFindingsSer::new(&findings, &policy_set),
+                timings: TimingsSer::from(&timings),
+            };
+            println!("{}", serde_json::to_string_pretty(&timed).unwrap());
+        } else if self.json_output {
+            findings.print_json(&policy_set);
+        } else {
+            findings.print_table();
+            if self.timings {
+                println!();
+                timings.print_table();
+            }
+        }
+        self.warn_unknown_queries();
+        Ok(())
+    }
+
+    /// Like `analyze_policyset`, but return the findings as JSON instead of printing them
+    pub(crate) fn analyze_policyset_json(
+        &self,
+        policy_set: &PolicySet,
+        request_env: &OpenRequestEnv,
+    ) -> Result<serde_json::Value, ExecError> {
+        let req_envs = request_env.to_request_envs(self.schema)?;
+        let findings = self.policyset_findings(policy_set, req_envs, &mut Timings::default())?;
+        Ok(serde_json::to_value(AnalyzePolicyFindingsSer::new(&findings, policy_set)).unwrap())
+    }
 
-        let req_envs = OpenRequestEnv::any().to_request_envs(self.schema)?;
+    /// Compute the findings of `analyze_policyset` for the given request environments, recording
+    /// the time spent in Lean for each policy and pair of policies in `timings`
+    fn policyset_findings(
+        &self,
+        policy_set: &PolicySet,
+        req_envs: Vec<RequestEnv>,
+        timings: &mut Timings,
+    ) -> Result<AnalyzePolicyFindings, ExecError> {
         let policies: Vec<&Policy> = policy_set.policies().collect();
 
-        for policy in policies.iter() {
-            let pvr = self.policy_vacuous(policy, &req_envs)?;
-            policy_vacuity_results.insert(policy.id().clone(), pvr);
+        let (policy_vacuity_results, mut policy_durations): (Vec<_>, Vec<_>) = self
+            .map_jobs(&policies, |analyzer, policy| {
+                analyzer.with_lean_time(|| analyzer.policy_vacuous(policy, &req_envs))
+            })?
+            .into_iter()
+            .unzip();
+        let policy_vacuity_results: HashMap<PolicyId, Vec<VacuityResult>> = zip(
+            policies.iter().map(|policy| policy.id().clone()),
+            policy_vacuity_results,
+        )
+        .collect();
+
+        // p |-> [err_1, err_2, ..., err_n] where err_i is how (and an environment in which) p errors for the ith request environment
+        let may_error_findings: HashMap<PolicyId, Vec<Option<ErrorResult>>> = if self.check_errors {
+            let (may_error_results, may_error_durations): (Vec<_>, Vec<_>) = self
+                .map_jobs(&policies, |analyzer, policy| {
+                    analyzer.with_lean_time(|| analyzer.policy_may_error(policy, &req_envs))
+                })?
+                .into_iter()
+                .unzip();
+            for (duration, may_error_duration) in zip(&mut policy_durations, may_error_durations) {
+                *duration += may_error_duration;
+            }
+            zip(
+                policies.iter().map(|policy| policy.id().clone()),
+                may_error_results,
+            )
+            .collect()
+        } else {
+            HashMap::new()
+        };
+        for (policy, duration) in zip(&policies, policy_durations) {
+            timings.push(TimedStep::Policy(policy.id().clone()), duration);
         }
 
         // p1 |-> [envF_1, envF_2, ..., envF_n] and p2 \in envF_i then p1 and p2 are equivalent for the ith request environment
@@ -78,20 +325,40 @@ impl<'a> Analyzer<'a> {
         // p1 |-> [envF_1, envF_2, ..., envF_n] and p2 \in envF_i then p2 shadows p1 for the ith request environment
         let mut forbid_shadowed_by_forbid_findings: HashMap<PolicyId, Vec<HashSet<PolicyId>>> =
             HashMap::new();
+        // p1 |-> [envF_1, envF_2, ..., envF_n] and p2 \in envF_i then the solver could not decide how p1 and p2 relate for the ith request environment
+        let mut undecided_findings: HashMap<PolicyId, Vec<HashSet<PolicyId>>> = HashMap::new();
+
+        let (policyset_vacuity_results, duration) =
+            self.with_lean_time(|| self.policyset_vacuous(policy_set, &req_envs))?;
+        timings.push(TimedStep::PolicySet, duration);
+
+        let policy_pairs: Vec<[&&Policy; 2]> = policies.iter().array_combinations().collect();
+        let (pair_results, pair_durations): (Vec<_>, Vec<_>) = self
+            .map_jobs(&policy_pairs, |analyzer, [policy1, policy2]| {
+                analyzer.with_lean_time(|| {
+                    analyzer.compute_pair_result(
+                        policy_set,
+                        policy1,
+                        policy2,
+                        &policy_vacuity_results,
+                        &req_envs,
+                    )
+                })
+            })?
+            .into_iter()
+            .unzip();
+        for ([policy1, policy2], duration) in zip(&policy_pairs, pair_durations) {
+            timings.push(
+                TimedStep::PolicyPair(policy1.id().clone(), policy2.id().clone()),
+                duration,
+            );
+        }
 
-        let policyset_vacuity_results = self.policyset_vacuous(&policy_set, &req_envs)?;
-
-        for [policy1, policy2] in policies.iter().array_combinations() {
-            let svr = policy_vacuity_results
-                .get(policy1.id())
-                .expect("Vacuousness of policy1 not precomputed");
-            let tvr = policy_vacuity_results
-                .get(policy2.id())
-                .expect("Vacuousness of policy2 not precomputed");
-            match (policy1.effect(), policy2.effect()) {
-                (Effect::Permit, Effect::Permit) => {
-                    let shadowing_results = self
-                        .compute_permit_shadowing_result(policy1, svr, policy2, tvr, &req_envs)?;
+        // Findings are collected sequentially, in the order of `policy_pairs`, so the
+        // result does not depend on the number of jobs
+        for ([policy1, policy2], pair_result) in zip(policy_pairs, pair_results) {
+            match pair_result {
+                PairResult::PermitShadowing(shadowing_results) => {
                     update_findings(
                         policy1.id(),
                         policy2.id(),
@@ -120,11 +387,15 @@ impl<'a> Analyzer<'a> {
                         &mut permit_shadowed_by_permit_findings,
                         ShadowingResult::Policy1Shadows2,
                     );
+                    update_findings(
+                        policy1.id(),
+                        policy2.id(),
+                        &shadowing_results,
+                        &mut undecided_findings,
+                        ShadowingResult::Unknown,
+                    );
                 }
-                (Effect::Permit, Effect::Forbid) => {
-                    let override_results = self.compute_forbid_overrides_shadow_result(
-                        policy2, tvr, policy1, svr, &req_envs,
-                    )?;
+                PairResult::Policy1OverriddenBy2(override_results) => {
                     update_findings(
                         policy1.id(),
                         policy2.id(),
@@ -132,11 +403,15 @@ impl<'a> Analyzer<'a> {
                         &mut permit_overridden_by_forbid_findings,
                         OverrideResult::Overrides,
                     );
+                    update_findings(
+                        policy1.id(),
+                        policy2.id(),
+                        &override_results,
+                        &mut undecided_findings,
+                        OverrideResult::Unknown,
+                    );
                 }
-                (Effect::Forbid, Effect::Permit) => {
-                    let override_results = self.compute_forbid_overrides_shadow_result(
-                        policy1, svr, policy2, tvr, &req_envs,
-                    )?;
+                PairResult::Policy2OverriddenBy1(override_results) => {
                     update_findings(
                         policy2.id(),
                         policy1.id(),
@@ -144,10 +419,15 @@ impl<'a> Analyzer<'a> {
                         &mut permit_overridden_by_forbid_findings,
                         OverrideResult::Overrides,
                     );
+                    update_findings(
+                        policy1.id(),
+                        policy2.id(),
+                        &override_results,
+                        &mut undecided_findings,
+                        OverrideResult::Unknown,
+                    );
                 }
-                (Effect::Forbid, Effect::Forbid) => {
-                    let shadowing_results = self
-                        .compute_forbid_shadowing_result(policy1, svr, policy2, tvr, &req_envs)?;
+                PairResult::ForbidShadowing(shadowing_results) => {
                     update_findings(
                         policy1.id(),
                         policy2.id(),
@@ -176,10 +456,17 @@ impl<'a> Analyzer<'a> {
                         &mut forbid_shadowed_by_forbid_findings,
                         ShadowingResult::Policy1Shadows2,
                     );
+                    update_findings(
+                        policy1.id(),
+                        policy2.id(),
+                        &shadowing_results,
+                        &mut undecided_findings,
+                        ShadowingResult::Unknown,
+                    );
                 }
             }
         }
-        let findings = AnalyzePolicyFindings::new(
+        Ok(AnalyzePolicyFindings::new(
             req_envs,
             policyset_vacuity_results,
             policy_vacuity_results,
@@ -187,13 +474,9 @@ impl<'a> Analyzer<'a> {
             permit_shadowed_by_permit_findings,
             permit_overridden_by_forbid_findings,
             forbid_shadowed_by_forbid_findings,
-        );
-        if self.json_output {
-            findings.print_json(&policy_set);
-        } else {
-            findings.print_table();
-        }
-        Ok(())
+            may_error_findings,
+            undecided_findings,
+        ))
     }
 }
 
@@ -204,6 +487,12 @@ pub(crate) struct PerSigFindings {
     pub(crate) permit_shadowed_by_permits: HashMap<PolicyId, HashSet<PolicyId>>,
     pub(crate) forbid_shadowed_by_forbids: HashMap<PolicyId, HashSet<PolicyId>>,
     pub(crate) permit_overridden_by_forbids: HashMap<PolicyId, HashSet<PolicyId>>,
+    /// Policies that error for every request their scope matches, with an example environment
+    pub(crate) always_errors: HashMap<PolicyId, Env>,
+    /// Policies that may error (but not always), with an example environment in which they do
+    pub(crate) may_error: HashMap<PolicyId, Env>,
+    /// Pairs of policies whose relationship the solver could not decide (e.g., it timed out)
+    pub(crate) undecided: HashMap<PolicyId, HashSet<PolicyId>>,
 }
 
 impl PerSigFindings {
@@ -213,6 +502,8 @@ impl PerSigFindings {
         permit_shadowed_by_permits: HashMap<PolicyId, HashSet<PolicyId>>,
         forbid_shadowed_by_forbids: HashMap<PolicyId, HashSet<PolicyId>>,
         permit_overridden_by_forbids: HashMap<PolicyId, HashSet<PolicyId>>,
+        errors: HashMap<PolicyId, ErrorResult>,
+        undecided: HashMap<PolicyId, HashSet<PolicyId>>,
     ) -> Self {
         let mut equiv_classes = Vec::new();
         while !equiv_results.is_empty() {
@@ -234,17 +525,29 @@ impl PerSigFindings {
             }
         }
 
+        let mut always_errors = HashMap::new();
+        let mut may_error = HashMap::new();
+        for (pid, error) in errors {
+            match error {
+                ErrorResult::AlwaysErrors(env) => always_errors.insert(pid, env),
+                ErrorResult::MayError(env) => may_error.insert(pid, env),
+            };
+        }
+
         Self {
             req_env: RequestEnvSer::new(&req_env),
             equiv_classes,
             permit_shadowed_by_permits,
             forbid_shadowed_by_forbids,
             permit_overridden_by_forbids,
+            always_errors,
+            may_error,
+            undecided,
         }
     }
 
     pub fn nfindings(&self) -> usize {
-        let mut ret = self.equiv_classes.len();
+        let mut ret = self.equiv_classes.len() + self.always_errors.len() + self.may_error.len();
         for (_, s) in self.permit_shadowed_by_permits.iter() {
             ret += s.len();
         }
@@ -254,6 +557,9 @@ impl PerSigFindings {
         for (_, s) in self.forbid_shadowed_by_forbids.iter() {
             ret += s.len();
         }
+        for (_, s) in self.undecided.iter() {
+            ret += s.len();
+        }
         ret
     }
 }
@@ -274,6 +580,8 @@ impl AnalyzePolicyFindings {
         permit_shadowed_by_permit_findings: HashMap<PolicyId, Vec<HashSet<PolicyId>>>,
         permit_overridden_by_forbid_findings: HashMap<PolicyId, Vec<HashSet<PolicyId>>>,
         forbid_shadowed_by_forbid_findings: HashMap<PolicyId, Vec<HashSet<PolicyId>>>,
+        may_error_findings: HashMap<PolicyId, Vec<Option<ErrorResult>>>,
+        undecided_findings: HashMap<PolicyId, Vec<HashSet<PolicyId>>>,
     ) -> Self {
         let vacuous_result = vacuous_finding_from_results(&vacuous_results);
         let vacuous_policies: HashMap<PolicyId, VacuityResult> = policy_vacuity_results
@@ -316,12 +624,32 @@ impl AnalyzePolicyFindings {
                 sig_forbid_shadowed_findings.insert(pid.clone(), fss.clone());
             }
 
+            let mut sig_may_error_findings = HashMap::new();
+            for (pid, mer) in may_error_findings.iter() {
+                let mes = mer
+                    .get(ind)
+                    .expect("Errors for policy not precomputed for signature");
+                if let Some(error) = mes {
+                    sig_may_error_findings.insert(pid.clone(), error.clone());
+                }
+            }
+
+            let mut sig_undecided_findings = HashMap::new();
+            for (pid, ur) in undecided_findings.iter() {
+                let us = ur
+                    .get(ind)
+                    .expect("Undecided pairs for policy not precomputed for signature");
+                sig_undecided_findings.insert(pid.clone(), us.clone());
+            }
+
             let sig_findings = PerSigFindings::new(
                 req_env.clone(),
                 sig_redundant_findings,
                 sig_permit_shadowed_findings,
                 sig_forbid_shadowed_findings,
                 sig_permit_overridden_findings,
+                sig_may_error_findings,
+                sig_undecided_findings,
             );
 
             // if there was actually something for this signature
@@ -346,18 +674,24 @@ impl AnalyzePolicyFindings {
             VacuityResult::MatchesNone => {
                 println!("Policyset is vacuous. Policyset denies all authorization requests.\n");
             }
+            VacuityResult::Unknown => {
+                println!(
+                    "Policyset may be vacuous. The solver could not decide whether it allows or denies all authorization requests.\n"
+                );
+            }
         }
 
-        if !self.vacuous_policies.is_empty() {
-            println!("Found {} vacuous policies:", self.vacuous_policies.len());
+        let (undecided_policies, vacuous_policies): (Vec<_>, Vec<_>) = self
+            .vacuous_policies
+            .iter()
+            .sorted_by_key(|(pid, _)| pid.to_string())
+            .partition(|(_, vr)| **vr == VacuityResult::Unknown);
+        if !vacuous_policies.is_empty() {
+            println!("Found {} vacuous policies:", vacuous_policies.len());
 
-            for (pid, vr) in self
-                .vacuous_policies
-                .iter()
-                .sorted_by_key(|(pid, _)| pid.to_string())
-            {
+            for (pid, vr) in vacuous_policies {
                 match vr {
-                    VacuityResult::MatchesSome => (),
+                    VacuityResult::MatchesSome | VacuityResult::Unknown => (),
                     VacuityResult::MatchesAll => {
                         println!("Policy `{pid}` applies to all authorization requests.")
                     }
@@ -368,6 +702,16 @@ impl AnalyzePolicyFindings {
             }
             println!()
         }
+        if !undecided_policies.is_empty() {
+            println!(
+                "Could not decide whether {} policies are vacuous:",
+                undecided_policies.len()
+            );
+            for (pid, _) in undecided_policies {
+                println!("Policy `{pid}` may apply to all or to no authorization requests.")
+            }
+            println!()
+        }
 
         let n_sig_findings = self
             .per_sig_findings
@@ -421,6 +765,38 @@ impl AnalyzePolicyFindings {
                     per_env_result_strs.push(result_str);
                 }
             }
+            for (pid, env) in sig_finding
+                .always_errors
+                .iter()
+                .sorted_by_key(|(pid, _)| pid.to_string())
+            {
+                let result_str = format!(
+                    "Policy `{pid}` always errors when its scope matches, e.g., for\n{}",
+                    ExampleEnv(env.clone()).to_string().trim_end()
+                );
+                per_env_result_strs.push(result_str);
+            }
+            for (pid, env) in sig_finding
+                .may_error
+                .iter()
+                .sorted_by_key(|(pid, _)| pid.to_string())
+            {
+                let result_str = format!(
+                    "Policy `{pid}` may error, e.g., for\n{}",
+                    ExampleEnv(env.clone()).to_string().trim_end()
+                );
+                per_env_result_strs.push(result_str);
+            }
+            for (pid, others) in sig_finding
+                .undecided
+                .iter()
+                .sorted_by_key(|(pid, _)| pid.to_string())
+            {
+                for opid in others.iter().sorted_by_key(|pid| pid.to_string()) {
+                    let result_str = format!("Could not decide how `{pid}` relates to `{opid}`");
+                    per_env_result_strs.push(result_str);
+                }
+            }
             table.add_row(Row::new(vec![
                 Cell::new(&sig_finding.req_env.principal_type),
                 Cell::new(&sig_finding.req_env.action_uid),
@@ -436,6 +812,15 @@ impl AnalyzePolicyFindings {
         let json = serde_json::to_string_pretty(&serializable_findings).unwrap();
         println!("{}", json);
     }
+
+    /// Print the findings as a SARIF 2.1.0 log, with results located in `policyset_file`
+    pub fn print_sarif(&self, policyset_file: &PathBuf) -> Result<(), ExecError> {
+        let spans = parse_policyset_spans(policyset_file)?;
+        let log = SarifLog::new(self, &policyset_file.display().to_string(), &spans);
+        let json = serde_json::to_string_pretty(&log).unwrap();
+        println!("{}", json);
+        Ok(())
+    }
 }
 
 fn ids_comma_sep(pids: &HashSet<PolicyId>) -> String {
@@ -461,15 +846,72 @@ pub enum VacuityResult {
     MatchesAll,
     MatchesSome,
     MatchesNone,
+    /// The solver could not decide which of the above holds (e.g., it timed out)
+    Unknown,
+}
+
+impl VacuityResult {
+    /// Combine the (possibly unknown) answers to whether all requests, and whether no requests,
+    /// are matched
+    fn from_decided(matches_all: Option<bool>, matches_none: Option<bool>) -> Self {
+        match (matches_all, matches_none) {
+            (Some(true), _) => VacuityResult::MatchesAll,
+            (_, Some(true)) => VacuityResult::MatchesNone,
+            (Some(false), Some(false)) => VacuityResult::MatchesSome,
+            _ => VacuityResult::Unknown,
+        }
+    }
+}
+
+/// A policy that errors for some requests either errors for every request its scope matches
+/// (AlwaysErrors), and so never applies, or only for some of them (MayError). Both come with an
+/// example environment in which the policy errors.
+#[derive(Clone, Debug)]
+pub(crate) enum ErrorResult {
+    AlwaysErrors(Env),
+    MayError(Env),
+}
+
+/// A policy with the scope of `policy`, matching exactly the requests for which the conditions of
+/// `policy` are evaluated without error: its conditions `c` are replaced by `when { c || true }`
+fn error_free_policy(policy: &Policy) -> Result<Policy, ExecError> {
+    let internal_error = |error: Box<dyn std::error::Error + Send + Sync>| {
+        ExecError::InternalAnalysisError { error }
+    };
+    let mut json = policy
+        .to_json()
+        .map_err(|err| internal_error(Box::new(err)))?;
+    let condition = json["conditions"]
+        .take()
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|clause| match clause["kind"].as_str() {
+            Some("unless") => serde_json::json!({ "!": { "arg": clause["body"] } }),
+            _ => clause["body"].clone(),
+        })
+        .reduce(|left, right| serde_json::json!({ "&&": { "left": left, "right": right } }))
+        .unwrap_or_else(|| serde_json::json!({ "Value": true }));
+    json["conditions"] = serde_json::json!([{
+        "kind": "when",
+        "body": { "||": { "left": condition, "right": { "Value": true } } },
+    }]);
+    Policy::from_json(Some(policy.id().clone()), json).map_err(|err| internal_error(Box::new(err)))
 }
 
 fn vacuous_finding_from_results(results: &[VacuityResult]) -> VacuityResult {
+    let any = |vr| results.contains(&vr);
     if results.iter().all(|res| *res == VacuityResult::MatchesAll) {
         VacuityResult::MatchesAll
     } else if results.iter().all(|res| *res == VacuityResult::MatchesNone) {
         VacuityResult::MatchesNone
-    } else {
+    } else if any(VacuityResult::MatchesSome)
+        || (any(VacuityResult::MatchesAll) && any(VacuityResult::MatchesNone))
+    {
         VacuityResult::MatchesSome
+    } else {
+        // vacuous, unless an unknown result is MatchesSome
+        VacuityResult::Unknown
     }
 }
 
@@ -483,21 +925,21 @@ impl<'a> Analyzer<'a> {
         let mut vr = Vec::new();
 
         for req_env in req_envs {
-            if self.lean_ffi.run_check_always_allows(
+            let allows_all = self.decided(self.lean_ffi.run_check_always_allows_timed(
                 policyset,
                 self.lean_schema.clone(),
                 req_env,
-            )? {
-                vr.push(VacuityResult::MatchesAll);
-            } else if self.lean_ffi.run_check_always_denies(
-                policyset,
-                self.lean_schema.clone(),
-                req_env,
-            )? {
-                vr.push(VacuityResult::MatchesNone);
+            ))?;
+            let denies_all = if allows_all == Some(true) {
+                None
             } else {
-                vr.push(VacuityResult::MatchesSome);
-            }
+                self.decided(self.lean_ffi.run_check_always_denies_timed(
+                    policyset,
+                    self.lean_schema.clone(),
+                    req_env,
+                ))?
+            };
+            vr.push(VacuityResult::from_decided(allows_all, denies_all));
         }
         Ok(vr)
     }
@@ -510,23 +952,61 @@ impl<'a> Analyzer<'a> {
     ) -> Result<Vec<VacuityResult>, ExecError> {
         let mut vr = Vec::new();
         for req_env in req_envs {
-            if self
-                .lean_ffi
-                .run_check_always_matches(policy, self.lean_schema.clone(), req_env)?
-            {
-                vr.push(VacuityResult::MatchesAll);
-            } else if self.lean_ffi.run_check_never_matches(
+            let matches_all = self.decided(self.lean_ffi.run_check_always_matches_timed(
                 policy,
                 self.lean_schema.clone(),
                 req_env,
-            )? {
-                vr.push(VacuityResult::MatchesNone);
+            ))?;
+            let matches_none = if matches_all == Some(true) {
+                None
             } else {
-                vr.push(VacuityResult::MatchesSome);
-            }
+                self.decided(self.lean_ffi.run_check_never_matches_timed(
+                    policy,
+                    self.lean_schema.clone(),
+                    req_env,
+                ))?
+            };
+            vr.push(VacuityResult::from_decided(matches_all, matches_none));
         }
         Ok(vr)
     }
+
+    /// Can a given Policy error (per request environment), returning whether it always errors
+    /// and an example environment if so. A policy whose errors cannot be decided is assumed not
+    /// to error, and one for which it cannot be decided whether it always errors may error.
+    fn policy_may_error(
+        &self,
+        policy: &Policy,
+        req_envs: &Vec<RequestEnv>,
+    ) -> Result<Vec<Option<ErrorResult>>, ExecError> {
+        let error_free = error_free_policy(policy)?;
+        let mut results = Vec::new();
+        for req_env in req_envs {
+            let Some(env) = self
+                .decided(self.lean_ffi.run_check_never_errors_with_cex_timed(
+                    policy,
+                    self.lean_schema.clone(),
+                    req_env,
+                ))?
+                .flatten()
+            else {
+                results.push(None);
+                continue;
+            };
+            // the policy always errors iff it is never evaluated without error
+            let always_errors = self.decided(self.lean_ffi.run_check_never_matches_timed(
+                &error_free,
+                self.lean_schema.clone(),
+                req_env,
+            ))?;
+            results.push(Some(if always_errors == Some(true) {
+                ErrorResult::AlwaysErrors(env)
+            } else {
+                ErrorResult::MayError(env)
+            }));
+        }
+        Ok(results)
+    }
 }
 
 /// Represents if policy1 is shadowed by policy2 or vice versa
@@ -540,12 +1020,29 @@ enum ShadowingResult {
     Policy2Shadows1,
     /// at least one policy is vacuous, or the policies allow incomparable sets of requests
     NoResult,
+    /// the solver could not decide which of the above holds (e.g., it timed out)
+    Unknown,
+}
+
+impl ShadowingResult {
+    /// Classify policies that are non vacuous by whether each one implies the other (if known)
+    fn from_implications(policy1_implies_2: Option<bool>, policy2_implies_1: Option<bool>) -> Self {
+        match (policy1_implies_2, policy2_implies_1) {
+            (Some(true), Some(true)) => ShadowingResult::Equivalent,
+            (Some(true), Some(false)) => ShadowingResult::Policy2Shadows1,
+            (Some(false), Some(true)) => ShadowingResult::Policy1Shadows2,
+            (Some(false), Some(false)) => ShadowingResult::NoResult,
+            _ => ShadowingResult::Unknown,
+        }
+    }
 }
 
 impl<'a> Analyzer<'a> {
-    /// Compute Redundant and Shadowed relationship between `policy1` and `policy2` (per environment)
+    /// Compute Redundant and Shadowed relationship between `policy1` and `policy2` of `policy_set`
+    /// (per environment)
     fn compute_permit_shadowing_result(
         &self,
+        policy_set: &PolicySet,
         policy1: &Policy,
         policy1_vacuity_results: &Vec<VacuityResult>,
         policy2: &Policy,
@@ -553,16 +1050,8 @@ impl<'a> Analyzer<'a> {
         req_envs: &Vec<RequestEnv>,
     ) -> Result<Vec<ShadowingResult>, ExecError> {
         let mut results = Vec::new();
-        let pset1 = PolicySet::from_policies([policy1.to_owned()]).map_err(|err| {
-            ExecError::PolicyIntoPolicySetError {
-                error: Box::new(err),
-            }
-        })?;
-        let pset2 = PolicySet::from_policies([policy2.to_owned()]).map_err(|err| {
-            ExecError::PolicyIntoPolicySetError {
-                error: Box::new(err),
-            }
-        })?;
+        let pset1 = singleton_policyset(policy_set, policy1)?;
+        let pset2 = singleton_policyset(policy_set, policy2)?;
 
         for ((vr1, vr2), req_env) in zip(
             zip(policy1_vacuity_results, policy2_vacuity_results),
@@ -572,6 +1061,9 @@ impl<'a> Analyzer<'a> {
                 (VacuityResult::MatchesNone, _) | (_, VacuityResult::MatchesNone) => {
                     results.push(ShadowingResult::NoResult)
                 }
+                (VacuityResult::Unknown, _) | (_, VacuityResult::Unknown) => {
+                    results.push(ShadowingResult::Unknown)
+                }
                 (VacuityResult::MatchesAll, VacuityResult::MatchesAll) => {
                     results.push(ShadowingResult::Equivalent)
                 }
@@ -582,24 +1074,22 @@ impl<'a> Analyzer<'a> {
                     results.push(ShadowingResult::Policy2Shadows1)
                 }
                 (VacuityResult::MatchesSome, VacuityResult::MatchesSome) => {
-                    let policy1shadows2 = self.lean_ffi.run_check_implies(
+                    let policy1shadows2 = self.decided(self.lean_ffi.run_check_implies_timed(
                         &pset1,
                         &pset2,
                         self.lean_schema.clone(),
                         req_env,
-                    )?;
-                    let policy2shadows1 = self.lean_ffi.run_check_implies(
+                    ))?;
+                    let policy2shadows1 = self.decided(self.lean_ffi.run_check_implies_timed(
                         &pset2,
                         &pset1,
                         self.lean_schema.clone(),
                         req_env,
-                    )?;
-                    match (policy1shadows2, policy2shadows1) {
-                        (true, true) => results.push(ShadowingResult::Equivalent),
-                        (true, false) => results.push(ShadowingResult::Policy2Shadows1),
-                        (false, true) => results.push(ShadowingResult::Policy1Shadows2),
-                        (false, false) => results.push(ShadowingResult::NoResult),
-                    }
+                    ))?;
+                    results.push(ShadowingResult::from_implications(
+                        policy1shadows2,
+                        policy2shadows1,
+                    ));
                 }
             }
         }
@@ -607,11 +1097,60 @@ impl<'a> Analyzer<'a> {
     }
 }
 
+/// The relationship between a pair of policies (per request environment), depending on their effects
+enum PairResult {
+    /// Both policies are permits
+    PermitShadowing(Vec<ShadowingResult>),
+    /// policy1 is a permit and policy2 is a forbid
+    Policy1OverriddenBy2(Vec<OverrideResult>),
+    /// policy1 is a forbid and policy2 is a permit
+    Policy2OverriddenBy1(Vec<OverrideResult>),
+    /// Both policies are forbids
+    ForbidShadowing(Vec<ShadowingResult>),
+}
+
+impl<'a> Analyzer<'a> {
+    /// Compute the relationship between `policy1` and `policy2` of `policy_set` (per request
+    /// environment)
+    fn compute_pair_result(
+        &self,
+        policy_set: &PolicySet,
+        policy1: &Policy,
+        policy2: &Policy,
+        policy_vacuity_results: &HashMap<PolicyId, Vec<VacuityResult>>,
+        req_envs: &Vec<RequestEnv>,
+    ) -> Result<PairResult, ExecError> {
+        let svr = policy_vacuity_results
+            .get(policy1.id())
+            .expect("Vacuousness of policy1 not precomputed");
+        let tvr = policy_vacuity_results
+            .get(policy2.id())
+            .expect("Vacuousness of policy2 not precomputed");
+        Ok(match (policy1.effect(), policy2.effect()) {
+            (Effect::Permit, Effect::Permit) => {
+                PairResult::PermitShadowing(self.compute_permit_shadowing_result(
+                    policy_set, policy1, svr, policy2, tvr, req_envs,
+                )?)
+            }
+            (Effect::Permit, Effect::Forbid) => PairResult::Policy1OverriddenBy2(
+                self.compute_forbid_overrides_shadow_result(policy2, tvr, policy1, svr, req_envs)?,
+            ),
+            (Effect::Forbid, Effect::Permit) => PairResult::Policy2OverriddenBy1(
+                self.compute_forbid_overrides_shadow_result(policy1, svr, policy2, tvr, req_envs)?,
+            ),
+            (Effect::Forbid, Effect::Forbid) => PairResult::ForbidShadowing(
+                self.compute_forbid_shadowing_result(policy1, svr, policy2, tvr, req_envs)?,
+            ),
+        })
+    }
+}
+
 /// Represents if the Forbid policy overrides the Permit policy
 #[derive(Clone, Copy, Debug, PartialEq)]
 enum OverrideResult {
     Overrides, // Forbid policy overrides Permit policy
     NoResult, // Either the Forbid or Permit policy is vacuous or there is a request allowed by the Permit policy that is not forbidden by the Forbid policy
+    Unknown,  // The solver could not decide which of the above holds (e.g., it timed out)
 }
 
 impl<'a> Analyzer<'a> {
@@ -632,11 +1171,12 @@ impl<'a> Analyzer<'a> {
             match (forbid_vr, permit_vr) {
             (VacuityResult::MatchesNone, _) | (VacuityResult::MatchesAll, _) |                                          // forbid policy is vacuous: does not apply or denies all
             (_, VacuityResult::MatchesNone) | (_, VacuityResult::MatchesAll) => results.push(OverrideResult::NoResult), // permit policy is vacuous: does not apply or allows all (no need to check overriding)
+            (VacuityResult::Unknown, _) | (_, VacuityResult::Unknown) => results.push(OverrideResult::Unknown), // vacuity of either policy is unknown
             _ => {
-                if self.lean_ffi.run_check_matches_implies(permit_policy, forbid_policy, self.lean_schema.clone(), req_env)? {
-                    results.push(OverrideResult::Overrides); // Every request allowed by permit is denied by forbid
-                } else {
-                    results.push(OverrideResult::NoResult);  // some request allowed by permit is not denied by forbid
+                match self.decided(self.lean_ffi.run_check_matches_implies_timed(permit_policy, forbid_policy, self.lean_schema.clone(), req_env))? {
+                    Some(true) => results.push(OverrideResult::Overrides), // Every request allowed by permit is denied by forbid
+                    Some(false) => results.push(OverrideResult::NoResult),  // some request allowed by permit is not denied by forbid
+                    None => results.push(OverrideResult::Unknown),
                 }
             }
         }
@@ -664,6 +1204,9 @@ impl<'a> Analyzer<'a> {
                 (VacuityResult::MatchesNone, _) | (_, VacuityResult::MatchesNone) => {
                     results.push(ShadowingResult::NoResult) // One of the two policies is vacuous
                 }
+                (VacuityResult::Unknown, _) | (_, VacuityResult::Unknown) => {
+                    results.push(ShadowingResult::Unknown) // The vacuity of one of the two policies is unknown
+                }
                 (VacuityResult::MatchesAll, VacuityResult::MatchesAll) => {
                     results.push(ShadowingResult::Equivalent) // Both policies deny all requests
                 }
@@ -674,24 +1217,24 @@ impl<'a> Analyzer<'a> {
                     results.push(ShadowingResult::Policy2Shadows1) // policy2 denies all requests, policy1 denies some
                 }
                 (VacuityResult::MatchesSome, VacuityResult::MatchesSome) => {
-                    let policy1shadows2 = self.lean_ffi.run_check_matches_implies(
-                        policy1,
-                        policy2,
-                        self.lean_schema.clone(),
-                        req_env,
-                    )?;
-                    let policy2shadows1 = self.lean_ffi.run_check_matches_implies(
-                        policy2,
-                        policy1,
-                        self.lean_schema.clone(),
-                        req_env,
-                    )?;
-                    match (policy1shadows2, policy2shadows1) {
-                        (true, true) => results.push(ShadowingResult::Equivalent), // Equivalent
-                        (true, false) => results.push(ShadowingResult::Policy2Shadows1), // policy2 denies strictly more than policy1
-                        (false, true) => results.push(ShadowingResult::Policy1Shadows2), // policy1 denies strictly more than policy2
-                        (false, false) => results.push(ShadowingResult::NoResult), // Incomparable
-                    }
+                    let policy1shadows2 =
+                        self.decided(self.lean_ffi.run_check_matches_implies_timed(
+                            policy1,
+                            policy2,
+                            self.lean_schema.clone(),
+                            req_env,
+                        ))?;
+                    let policy2shadows1 =
+                        self.decided(self.lean_ffi.run_check_matches_implies_timed(
+                            policy2,
+                            policy1,
+                            self.lean_schema.clone(),
+                            req_env,
+                        ))?;
+                    results.push(ShadowingResult::from_implications(
+                        policy1shadows2,
+                        policy2shadows1,
+                    ));
                 }
             }
         }
@@ -770,7 +1313,7 @@ fn display_entity(
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(transparent)]
-struct ExampleEnv(cedar_lean_ffi::Env);
+pub(crate) struct ExampleEnv(pub(crate) cedar_lean_ffi::Env);
 
 impl std::fmt::Display for ExampleEnv {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -839,6 +1382,8 @@ enum PolicySetComparisonStatus {
         /// Env where the first policyset denies and the second allows
         less_permissive_example: ExampleEnv,
     },
+    /// The solver could not decide how the policysets compare (e.g., it timed out)
+    Unknown,
 }
 
 impl PolicySetComparisonStatus {
@@ -859,8 +1404,207 @@ impl PolicySetComparisonStatus {
                     "pset1 is incomparable with pset2\nExample where pset1 is more permissive: {more_permissive_example}\nExample where pset1 is less permissive: {less_permissive_example}"
                 )
             }
+            PolicySetComparisonStatus::Unknown => {
+                String::from("unknown (the solver could not decide the comparison)")
+            }
+        }
+    }
+}
+
+impl PolicySetComparisonStatus {
+    /// The example environments where the two policysets differ
+    fn examples(&self) -> Vec<(Difference, &ExampleEnv)> {
+        match self {
+            PolicySetComparisonStatus::MorePermissive { example } => {
+                vec![(Difference::OnlyPset1Allows, example)]
+            }
+            PolicySetComparisonStatus::LessPermissive { example } => {
+                vec![(Difference::OnlyPset2Allows, example)]
+            }
+            PolicySetComparisonStatus::Equivalent | PolicySetComparisonStatus::Unknown => vec![],
+            PolicySetComparisonStatus::Incomparable {
+                more_permissive_example,
+                less_permissive_example,
+            } => vec![
+                (Difference::OnlyPset1Allows, more_permissive_example),
+                (Difference::OnlyPset2Allows, less_permissive_example),
+            ],
+        }
+    }
+}
+
+/// Which of the two policysets allows an example where they differ
+#[derive(Debug, Clone, Copy, Serialize)]
+enum Difference {
+    OnlyPset1Allows,
+    OnlyPset2Allows,
+}
+
+impl std::fmt::Display for Difference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Difference::OnlyPset1Allows => write!(f, "pset1 allows and pset2 denies"),
+            Difference::OnlyPset2Allows => write!(f, "pset1 denies and pset2 allows"),
+        }
+    }
+}
+
+/// How a policy (matched by id) differs between the two policysets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+enum PolicyChange {
+    /// Only in pset2
+    Added,
+    /// Only in pset1
+    Removed,
+    /// In both policysets, with different definitions
+    Changed,
+    /// In both policysets, with the same definition
+    Unchanged,
+}
+
+impl PolicyChange {
+    /// Policies are compared in their JSON form, so that reformatting or commenting a policy does not change it
+    fn new(pid: &PolicyId, pset1: &PolicySet, pset2: &PolicySet) -> Self {
+        match (pset1.policy(pid), pset2.policy(pid)) {
+            (Some(_), None) => PolicyChange::Removed,
+            (None, Some(_)) => PolicyChange::Added,
+            (Some(p1), Some(p2))
+                if p1
+                    .to_json()
+                    .ok()
+                    .is_some_and(|json| p2.to_json().ok() == Some(json)) =>
+            {
+                PolicyChange::Unchanged
+            }
+            _ => PolicyChange::Changed,
+        }
+    }
+}
+
+/// One example difference, attributed to the policies determining the decision of each policyset
+#[derive(Debug, Clone, Serialize)]
+struct DifferenceAttribution {
+    req_env: RequestEnvSer,
+    difference: Difference,
+    /// Policies determining the decision of pset1 (as reported by the Rust `Authorizer`)
+    pset1_determining: Vec<PolicyId>,
+    /// Policies determining the decision of pset2 (as reported by the Rust `Authorizer`)
+    pset2_determining: Vec<PolicyId>,
+}
+
+/// The differences a policy is (partly) responsible for
+#[derive(Debug, Clone, Serialize)]
+struct PolicyAttribution {
+    policy_id: PolicyId,
+    change: PolicyChange,
+    differences: Vec<DifferenceAttribution>,
+}
+
+/// Determining policies of `policy_set` for the request and entities of `example`
+fn determining_policies(
+    policy_set: &PolicySet,
+    example: &ExampleEnv,
+) -> Result<Vec<PolicyId>, ExecError> {
+    let (request, entities) = example.0.clone().to_request_and_entities().map_err(|err| {
+        ExecError::InternalAnalysisError {
+            error: format!("invalid example environment: {err}").into(),
+        }
+    })?;
+    let response = Authorizer::new().is_authorized(&request, policy_set, &entities);
+    Ok(response.diagnostics().reason().cloned().sorted().collect())
+}
+
+/// Attribute every difference in `results` to the added, removed or changed policies
+/// that determine the decision of either policyset, grouped by policy id.
+///
+/// Unchanged policies evaluate identically in both policysets, so they cannot cause a difference.
+fn attribute_differences(
+    pset1: &PolicySet,
+    pset2: &PolicySet,
+    results: &[PolicySetComparisonResult],
+) -> Result<Vec<PolicyAttribution>, ExecError> {
+    let mut attributions: BTreeMap<PolicyId, PolicyAttribution> = BTreeMap::new();
+    for res in results.iter() {
+        for (difference, example) in res.status.examples() {
+            let attribution = DifferenceAttribution {
+                req_env: res.req_env.clone(),
+                difference,
+                pset1_determining: determining_policies(pset1, example)?,
+                pset2_determining: determining_policies(pset2, example)?,
+            };
+            let responsible: HashSet<&PolicyId> = attribution
+                .pset1_determining
+                .iter()
+                .chain(attribution.pset2_determining.iter())
+                .collect();
+            for pid in responsible {
+                let change = PolicyChange::new(pid, pset1, pset2);
+                if change == PolicyChange::Unchanged {
+                    continue;
+                }
+                attributions
+                    .entry(pid.clone())
+                    .or_insert_with(|| PolicyAttribution {
+                        policy_id: pid.clone(),
+                        change,
+                        differences: Vec::new(),
+                    })
+                    .differences
+                    .push(attribution.clone());
+            }
         }
     }
+    Ok(attributions.into_values().collect())
+}
+
+fn ids_or_none(pids: &[PolicyId]) -> String {
+    if pids.is_empty() {
+        String::from("none")
+    } else {
+        pids.iter().map(|pid| format!("`{pid}`")).join(", ")
+    }
+}
+
+fn print_attributions(attributions: &[PolicyAttribution]) {
+    println!(
+        "Found {} policies responsible for differences:",
+        attributions.len()
+    );
+    let mut table = Table::new();
+    // Print a nice header
+    table.add_row(Row::new(vec![
+        Cell::new("Policy").with_style(Attr::Bold),
+        Cell::new("Change").with_style(Attr::Bold),
+        Cell::new("Differences").with_style(Attr::Bold),
+    ]));
+    for attribution in attributions.iter() {
+        let differences = attribution
+            .differences
+            .iter()
+            .map(|diff| {
+                format!(
+                    "{} for {}\n  determining in pset1: {}\n  determining in pset2: {}",
+                    diff.difference,
+                    diff.req_env,
+                    ids_or_none(&diff.pset1_determining),
+                    ids_or_none(&diff.pset2_determining),
+                )
+            })
+            .join("\n");
+        table.add_row(Row::new(vec![
+            Cell::new(&attribution.policy_id.to_string()),
+            Cell::new(&format!("{:?}", attribution.change)),
+            Cell::new(&differences),
+        ]));
+    }
+    table.printstd();
+}
+
+/// JSON output of `compare_policysets` when differences are attributed to policies
+#[derive(Debug, Serialize)]
+struct AttributedComparisonResults<'a> {
+    results: &'a [PolicySetComparisonResult],
+    attributions: Vec<PolicyAttribution>,
 }
 
 #[derive(Debug, Serialize)]
@@ -891,48 +1635,411 @@ fn print_compare_results(results: &[PolicySetComparisonResult]) {
 }
 
 impl<'a> Analyzer<'a> {
-    /// Compare `pset1` to `pset2` and print results
-    pub fn compare_policysets(&self, pset1: PolicySet, pset2: PolicySet) -> Result<(), ExecError> {
-        let req_envs = OpenRequestEnv::any().to_request_envs(self.schema)?;
-        let comparison_results: Vec<PolicySetComparisonResult> = req_envs
-            .iter()
-            .map(|req_env| -> Result<PolicySetComparisonResult, ExecError> {
-                let fwd_implies = self.lean_ffi.run_check_implies_with_cex(
-                    &pset1,
-                    &pset2,
+    /// Compare `pset1` to `pset2`, restricted to the request environments described by `request_env`, and print results
+    pub fn compare_policysets(
+        &self,
+        pset1: PolicySet,
+        pset2: PolicySet,
+        request_env: &OpenRequestEnv,
+    ) -> Result<(), ExecError> {
+        let req_envs = request_env.to_request_envs(self.schema)?;
+        let mut timings = Timings::default();
+        let comparison_results =
+            self.comparison_results(&pset1, &pset2, &req_envs, &mut timings)?;
+        let attributions = if self.attribute_differences {
+            Some(attribute_differences(&pset1, &pset2, &comparison_results)?)
+        } else {
+            None
+        };
+        if self.json_output {
+            let json = match attributions {
+                Some(attributions) => serde_json::to_value(AttributedComparisonResults {
+                    results: &comparison_results,
+                    attributions,
+                }),
+                None => serde_json::to_value(&comparison_results),
+            }
+            .unwrap();
+            if self.timings {
+                let timed = TimedOutputSer {
+                    results: json,
+                    timings: TimingsSer::from(&timings),
+                };
+                serde_json::to_writer_pretty(std::io::stdout(), &timed).unwrap();
+            } else {
+                serde_json::to_writer_pretty(std::io::stdout(), &json).unwrap();
+            }
+        } else {
+            print_compare_results(&comparison_results);
+            if let Some(attributions) = attributions {
+                println!();
+                print_attributions(&attributions);
+            }
+            if self.timings {
+                println!();
+                timings.print_table();
+            }
+        }
+        self.warn_unknown_queries();
+        Ok(())
+    }
+
+    /// Like `compare_policysets`, but return the results as JSON instead of printing them
+    pub(crate) fn compare_policysets_json(
+        &self,
+        pset1: &PolicySet,
+        pset2: &PolicySet,
+        request_env: &OpenRequestEnv,
+    ) -> Result<serde_json::Value, ExecError> {
+        let req_envs = request_env.to_request_envs(self.schema)?;
+        let comparison_results =
+            self.comparison_results(pset1, pset2, &req_envs, &mut Timings::default())?;
+        let json = if self.attribute_differences {
+            serde_json::to_value(AttributedComparisonResults {
+                results: &comparison_results,
+                attributions: attribute_differences(pset1, pset2, &comparison_results)?,
+            })
+        } else {
+            serde_json::to_value(&comparison_results)
+        };
+        Ok(json.unwrap())
+    }
+
+    /// Compare `pset1` to `pset2` for each of the given request environments, recording the time
+    /// spent in Lean for each of them in `timings`
+    fn comparison_results(
+        &self,
+        pset1: &PolicySet,
+        pset2: &PolicySet,
+        req_envs: &[RequestEnv],
+        timings: &mut Timings,
+    ) -> Result<Vec<PolicySetComparisonResult>, ExecError> {
+        let mut results = Vec::with_capacity(req_envs.len());
+        for req_env in req_envs {
+            let (result, duration) = self.with_lean_time(|| {
+                let fwd_implies = self.decided(self.lean_ffi.run_check_implies_with_cex_timed(
+                    pset1,
+                    pset2,
                     self.lean_schema.clone(),
                     req_env,
-                )?;
-                let bwd_implies = self.lean_ffi.run_check_implies_with_cex(
-                    &pset2,
-                    &pset1,
+                ))?;
+                let bwd_implies = self.decided(self.lean_ffi.run_check_implies_with_cex_timed(
+                    pset2,
+                    pset1,
                     self.lean_schema.clone(),
                     req_env,
-                )?;
+                ))?;
                 let status = match (fwd_implies, bwd_implies) {
-                    (None, None) => PolicySetComparisonStatus::Equivalent,
-                    (None, Some(cex)) => PolicySetComparisonStatus::LessPermissive {
+                    (Some(None), Some(None)) => PolicySetComparisonStatus::Equivalent,
+                    (Some(None), Some(Some(cex))) => PolicySetComparisonStatus::LessPermissive {
                         example: ExampleEnv(cex),
                     },
-                    (Some(cex), None) => PolicySetComparisonStatus::MorePermissive {
+                    (Some(Some(cex)), Some(None)) => PolicySetComparisonStatus::MorePermissive {
                         example: ExampleEnv(cex),
                     },
-                    (Some(more_cex), Some(less_cex)) => PolicySetComparisonStatus::Incomparable {
-                        more_permissive_example: ExampleEnv(more_cex),
-                        less_permissive_example: ExampleEnv(less_cex),
-                    },
+                    (Some(Some(more_cex)), Some(Some(less_cex))) => {
+                        PolicySetComparisonStatus::Incomparable {
+                            more_permissive_example: ExampleEnv(more_cex),
+                            less_permissive_example: ExampleEnv(less_cex),
+                        }
+                    }
+                    (None, _) | (_, None) => PolicySetComparisonStatus::Unknown,
                 };
                 Ok(PolicySetComparisonResult {
                     req_env: RequestEnvSer::new(req_env),
                     status,
                 })
-            })
-            .collect::<Result<Vec<PolicySetComparisonResult>, ExecError>>()?;
-        if self.json_output {
-            serde_json::to_writer_pretty(std::io::stdout(), &comparison_results).unwrap();
+            })?;
+            timings.push(TimedStep::ReqEnv(RequestEnvSer::new(req_env)), duration);
+            results.push(result);
+        }
+        Ok(results)
+    }
+}
+
+/// Whether `minimize_policyset` removed a candidate policy
+#[derive(Debug, Clone, Serialize)]
+enum RemovalStatus {
+    /// The remaining policies are equivalent to the original PolicySet without this policy
+    Removed,
+    /// Removing this policy changes the authorization decision for some request in `req_env`
+    Kept { req_env: RequestEnvSer },
+    /// The solver could not decide whether removing this policy changes the authorization decision
+    /// for some request in `req_env` (e.g., it timed out), so the policy is kept
+    Unknown { req_env: RequestEnvSer },
+}
+
+/// A policy flagged as redundant, shadowed or overridden, and what `minimize_policyset` did with it
+#[derive(Debug, Clone, Serialize)]
+struct RemovalReport {
+    policy_id: PolicyId,
+    /// The findings that made the policy a candidate for removal
+    reasons: Vec<String>,
+    status: RemovalStatus,
+}
+
+/// JSON result of `minimize_policyset_json`
+#[derive(Debug, Serialize)]
+struct MinimizeResult {
+    /// The minimized PolicySet, in the JSON policy set format
+    policies: serde_json::Value,
+    removals: Vec<RemovalReport>,
+}
+
+/// `policy_set` in the JSON policy set format, which keeps the ids of its policies, templates and links
+fn policyset_json(policy_set: &PolicySet) -> Result<serde_json::Value, ExecError> {
+    policy_set
+        .clone()
+        .to_json()
+        .map_err(|err| ExecError::PolicyIntoPolicySetError {
+            error: Box::new(err),
+        })
+}
+
+/// `policy_set` in the Cedar policy format, with an `@id` annotation on every policy and template
+/// that lacks one so that it keeps its id when parsed again. Returns `None` if `policy_set` has
+/// template-linked policies, which this format cannot express.
+fn policyset_text(policy_set: &PolicySet) -> Option<String> {
+    if policy_set.policies().any(|policy| !policy.is_static()) {
+        return None;
+    }
+    let with_id = |id: &PolicyId, annotated: bool, text: String| {
+        if annotated {
+            text
         } else {
-            print_compare_results(&comparison_results);
+            format!("@id({:?})\n{text}", id.to_string())
+        }
+    };
+    let templates = policy_set.templates().map(|template| {
+        with_id(
+            template.id(),
+            template.annotation("id").is_some(),
+            template.to_string(),
+        )
+    });
+    let policies = policy_set.policies().map(|policy| {
+        with_id(
+            policy.id(),
+            policy.annotation("id").is_some(),
+            policy.to_string(),
+        )
+    });
+    Some(templates.chain(policies).join("\n\n"))
+}
+
+fn print_minimize_report(reports: &[RemovalReport]) {
+    let nremoved = reports
+        .iter()
+        .filter(|report| matches!(report.status, RemovalStatus::Removed))
+        .count();
+    println!(
+        "Removed {nremoved} of {} candidate policies:",
+        reports.len()
+    );
+    let mut table = Table::new();
+    // Print a nice header
+    table.add_row(Row::new(vec![
+        Cell::new("Policy").with_style(Attr::Bold),
+        Cell::new("Result").with_style(Attr::Bold),
+        Cell::new("Findings").with_style(Attr::Bold),
+    ]));
+    for report in reports.iter() {
+        let result = match &report.status {
+            RemovalStatus::Removed => String::from("Removed"),
+            RemovalStatus::Kept { req_env } => format!("Kept, needed for {req_env}"),
+            RemovalStatus::Unknown { req_env } => {
+                format!("Kept, could not decide if needed for {req_env}")
+            }
+        };
+        table.add_row(Row::new(vec![
+            Cell::new(&report.policy_id.to_string()),
+            Cell::new(&result),
+            Cell::new(&report.reasons.join("\n")),
+        ]));
+    }
+    table.printstd();
+}
+
+/// Collect, per policy, the findings that make it a candidate for removal
+fn removal_candidates(findings: &AnalyzePolicyFindings) -> BTreeMap<PolicyId, Vec<String>> {
+    let mut candidates: BTreeMap<PolicyId, Vec<String>> = BTreeMap::new();
+    for sig_finding in findings.per_sig_findings.iter() {
+        let req_env = &sig_finding.req_env;
+        for equiv_class in sig_finding.equiv_classes.iter() {
+            for pid in equiv_class.iter() {
+                let others = equiv_class.iter().filter(|opid| *opid != pid);
+                candidates.entry(pid.clone()).or_default().push(format!(
+                    "Redundant with {} for {req_env}",
+                    others.map(|opid| format!("`{opid}`")).sorted().join(", ")
+                ));
+            }
+        }
+        for (verb, pairs) in [
+            ("Shadowed by", &sig_finding.permit_shadowed_by_permits),
+            ("Overridden by", &sig_finding.permit_overridden_by_forbids),
+            ("Shadowed by", &sig_finding.forbid_shadowed_by_forbids),
+        ] {
+            for (pid, others) in pairs.iter() {
+                for opid in others.iter().sorted() {
+                    candidates
+                        .entry(pid.clone())
+                        .or_default()
+                        .push(format!("{verb} `{opid}` for {req_env}"));
+                }
+            }
+        }
+    }
+    candidates
+}
+
+impl<'a> Analyzer<'a> {
+    /// Greedily remove policies that `analyze_policyset` flags as redundant, shadowed or overridden
+    /// for the request environments described by `request_env`, keeping a removal only if the
+    /// remaining policies are still equivalent to `policy_set` for every request environment of the
+    /// schema. The minimized PolicySet is written to `output_file` (in the JSON policy set format if
+    /// its name ends in `.json`) and a report of the removals is printed.
+    pub fn minimize_policyset(
+        &self,
+        policy_set: PolicySet,
+        request_env: &OpenRequestEnv,
+        output_file: &PathBuf,
+    ) -> Result<(), ExecError> {
+        let req_envs = request_env.to_request_envs(self.schema)?;
+        let mut timings = Timings::default();
+        let (minimized, reports) = self.minimized_policyset(&policy_set, req_envs, &mut timings)?;
+
+        let write_error =
+            |error: Box<dyn std::error::Error + Send + Sync>| ExecError::FileWriteError {
+                content_type: ContentType::PolicySet,
+                file_name: output_file.clone(),
+                error,
+            };
+        let contents = if is_json_file(output_file) {
+            serde_json::to_string_pretty(&policyset_json(&minimized)?).unwrap()
+        } else {
+            policyset_text(&minimized).ok_or_else(|| {
+                write_error(
+                    "template-linked policies can only be written in the JSON policy set format (use a file ending in .json)"
+                        .into(),
+                )
+            })?
+        };
+        std::fs::write(output_file, format!("{contents}\n"))
+            .map_err(|err| write_error(Box::new(err)))?;
+
+        if self.json_output && self.timings {
+            let timed = TimedOutputSer {
+                results: &reports,
+                timings: TimingsSer::from(&timings),
+            };
+            serde_json::to_writer_pretty(std::io::stdout(), &timed).unwrap();
+        } else if self.json_output {
+            serde_json::to_writer_pretty(std::io::stdout(), &reports).unwrap();
+        } else {
+            print_minimize_report(&reports);
+            println!(
+                "\nWrote minimized PolicySet ({} of {} policies) to {}",
+                minimized.policies().count(),
+                policy_set.policies().count(),
+                output_file.display()
+            );
+            if self.timings {
+                println!();
+                timings.print_table();
+            }
         }
+        self.warn_unknown_queries();
         Ok(())
     }
+
+    /// Like `minimize_policyset`, but return the minimized PolicySet and the report of the
+    /// removals as JSON instead of writing and printing them
+    pub(crate) fn minimize_policyset_json(
+        &self,
+        policy_set: &PolicySet,
+        request_env: &OpenRequestEnv,
+    ) -> Result<serde_json::Value, ExecError> {
+        let req_envs = request_env.to_request_envs(self.schema)?;
+        let (minimized, reports) =
+            self.minimized_policyset(policy_set, req_envs, &mut Timings::default())?;
+        Ok(serde_json::to_value(MinimizeResult {
+            policies: policyset_json(&minimized)?,
+            removals: reports,
+        })
+        .unwrap())
+    }
+
+    /// Compute the minimized PolicySet of `minimize_policyset`, from the findings for the given
+    /// request environments, recording the time spent in Lean for the findings and for checking
+    /// each removal in `timings`
+    fn minimized_policyset(
+        &self,
+        policy_set: &PolicySet,
+        req_envs: Vec<RequestEnv>,
+        timings: &mut Timings,
+    ) -> Result<(PolicySet, Vec<RemovalReport>), ExecError> {
+        let findings = self.policyset_findings(policy_set, req_envs, timings)?;
+        // The minimized PolicySet replaces the original one for all requests, not only those in
+        // `req_envs`, so removals are checked against every request environment of the schema
+        let all_req_envs = OpenRequestEnv::any().to_request_envs(self.schema)?;
+
+        let mut minimized = policy_set.clone();
+        let mut reports = Vec::new();
+        for (pid, reasons) in removal_candidates(&findings) {
+            // Template-linked policies are unlinked, keeping their template
+            let mut candidate = minimized.clone();
+            if minimized.policy(&pid).is_some_and(Policy::is_static) {
+                candidate.remove_static(pid.clone())
+            } else {
+                candidate.unlink(pid.clone())
+            }
+            .map_err(|err| ExecError::PolicyIntoPolicySetError {
+                error: Box::new(err),
+            })?;
+            let (status, duration) =
+                self.with_lean_time(|| self.removal_status(policy_set, &candidate, &all_req_envs))?;
+            timings.push(TimedStep::Removal(pid.clone()), duration);
+            if matches!(status, RemovalStatus::Removed) {
+                minimized = candidate;
+            }
+            reports.push(RemovalReport {
+                policy_id: pid,
+                reasons,
+                status,
+            });
+        }
+        Ok((minimized, reports))
+    }
+
+    /// Whether `candidate` (the PolicySet with a policy removed) is equivalent to `policy_set` for
+    /// every request environment in `req_envs`
+    fn removal_status(
+        &self,
+        policy_set: &PolicySet,
+        candidate: &PolicySet,
+        req_envs: &[RequestEnv],
+    ) -> Result<RemovalStatus, ExecError> {
+        for req_env in req_envs {
+            let equivalent = self.decided(self.lean_ffi.run_check_equivalent_timed(
+                policy_set,
+                candidate,
+                self.lean_schema.clone(),
+                req_env,
+            ))?;
+            match equivalent {
+                Some(true) => continue,
+                Some(false) => {
+                    return Ok(RemovalStatus::Kept {
+                        req_env: RequestEnvSer::new(req_env),
+                    });
+                }
+                None => {
+                    return Ok(RemovalStatus::Unknown {
+                        req_env: RequestEnvSer::new(req_env),
+                    });
+                }
+            }
+        }
+        Ok(RemovalStatus::Removed)
+    }
 }
@@ -14,10 +14,14 @@
  * limitations under the License.
  */
 use crate::err::ExecError;
+use crate::export::CexExport;
+use crate::symcc::CheckOptions;
 use crate::util;
+use cedar_policy::Schema;
 use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum};
-use serde::Serialize;
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use std::num::{NonZeroU64, NonZeroUsize};
+use std::path::{Path, PathBuf};
 
 #[derive(Args, Clone, Debug, Serialize)]
 pub(crate) struct PolicyAnalysisArgs {
@@ -27,6 +31,9 @@ pub(crate) struct PolicyAnalysisArgs {
     /// A file containing the schema for which the Policy is to be analyzed against
     #[clap(required = true)]
     pub(crate) schema_file: PathBuf,
+    /// Whether to output the analysis results in .json format
+    #[clap(long, short, action=ArgAction::SetTrue)]
+    pub(crate) json_output: bool,
 }
 
 #[derive(Args, Clone, Debug, Serialize)]
@@ -34,6 +41,9 @@ pub(crate) struct PolicySetAnalysisArgs {
     /// A file containing the PolicySet to be analyzed
     #[clap(required = true)]
     pub(crate) policyset_file: PathBuf,
+    /// A JSON file of template-link instantiations to link the templates of the PolicySet with
+    #[clap(long = "links", value_name = "FILE")]
+    pub(crate) links_file: Option<PathBuf>,
     /// A file containing the schema for which the PolicySet is to be analyzed against
     #[clap(required = true)]
     pub(crate) schema_file: PathBuf,
@@ -50,6 +60,9 @@ pub(crate) struct ComparePolicySetAnalysisArgs {
     /// A file containing the second PolicySet to be analyzed
     #[clap(required = true)]
     pub(crate) pset2_file: PathBuf,
+    /// A JSON file of template-link instantiations to link the templates of the PolicySets with
+    #[clap(long = "links", value_name = "FILE")]
+    pub(crate) links_file: Option<PathBuf>,
     /// A file containing the schema for which the PolicySet(s) are to be analyzed against
     #[clap(required = true)]
     pub(crate) schema_file: PathBuf,
@@ -58,6 +71,88 @@ pub(crate) struct ComparePolicySetAnalysisArgs {
     pub(crate) json_output: bool,
 }
 
+#[derive(Args, Clone, Debug, Serialize)]
+pub(crate) struct PropertyAnalysisArgs {
+    /// A file containing the PolicySet to be analyzed
+    #[clap(required = true)]
+    pub(crate) policyset_file: PathBuf,
+    /// A file containing a Cedar boolean expression over principal, action, resource and context
+    /// that must hold (without erroring) for every request allowed by the PolicySet
+    #[clap(required = true)]
+    pub(crate) property_file: PathBuf,
+    /// A JSON file of template-link instantiations to link the templates of the PolicySet with
+    #[clap(long = "links", value_name = "FILE")]
+    pub(crate) links_file: Option<PathBuf>,
+    /// A file containing the schema for which the PolicySet is to be analyzed against
+    #[clap(required = true)]
+    pub(crate) schema_file: PathBuf,
+    /// Whether to output the analysis results in .json format
+    #[clap(long, short, action=ArgAction::SetTrue)]
+    pub(crate) json_output: bool,
+}
+
+#[derive(Args, Clone, Debug, Serialize)]
+pub(crate) struct ComparePolicyAnalysisArgs {
+    /// A file containing the first Policy to be analyzed
+    #[clap(required = true)]
+    pub(crate) policy1_file: PathBuf,
+    /// A file containing the second Policy to be analyzed
+    #[clap(required = true)]
+    pub(crate) policy2_file: PathBuf,
+    /// A file containing the schema for which the Policies are to be analyzed against
+    #[clap(required = true)]
+    pub(crate) schema_file: PathBuf,
+    /// Treat `policy1_file` as a PolicySet and analyze the Policy with this id
+    #[clap(long, value_name = "POLICY_ID")]
+    pub(crate) policy_id1: Option<String>,
+    /// Treat `policy2_file` as a PolicySet and analyze the Policy with this id
+    #[clap(long, value_name = "POLICY_ID")]
+    pub(crate) policy_id2: Option<String>,
+    /// Whether to output the analysis results in .json format
+    #[clap(long, short, action=ArgAction::SetTrue)]
+    pub(crate) json_output: bool,
+}
+
+#[derive(Args, Clone, Debug, Serialize)]
+#[clap(next_help_heading = "Counterexample Options")]
+pub(crate) struct CexArgs {
+    /// Report a concrete counterexample for each request environment where the check does not hold
+    #[arg(long)]
+    pub(crate) cex: bool,
+    /// Write each counterexample as an integration test case (schema, policies, entities and JsonTest) in this directory
+    #[arg(long, value_name = "DIR", requires = "cex")]
+    pub(crate) export_cex: Option<PathBuf>,
+    /// Report up to N counterexamples per request environment, each with a distinct principal/resource pair (counterexamples differing only in context or entities are not distinguished)
+    #[arg(long, value_name = "N", default_value = "1", requires = "cex")]
+    pub(crate) max_cex: NonZeroUsize,
+    /// Shrink each counterexample to a minimal one with the same authorization outcome
+    #[arg(long, requires = "cex")]
+    pub(crate) minimize_cex: bool,
+}
+
+impl CexArgs {
+    /// The options of a `symcc` check against `schema` (parsed from `schema_file`)
+    pub(crate) fn check_options(
+        &self,
+        schema: &Schema,
+        schema_file: &Path,
+        json_output: bool,
+        timings: bool,
+    ) -> CheckOptions {
+        CheckOptions {
+            cex: self.cex,
+            max_cex: self.max_cex,
+            minimize: self.minimize_cex.then(|| schema.clone()),
+            json_output,
+            export: self.export_cex.as_ref().map(|dirname| CexExport {
+                dirname: dirname.clone(),
+                schema_file: schema_file.to_path_buf(),
+            }),
+            timings,
+        }
+    }
+}
+
 #[derive(Args, Clone, Debug, Serialize)]
 #[clap(next_help_heading = "Execution Modes")]
 pub(crate) struct Mode {
@@ -67,6 +162,23 @@ pub(crate) struct Mode {
     /// Print the SMT formula produced by the provided backend
     #[arg(long, conflicts_with_all = ["run_analysis"], global=true)]
     print_smtlib: bool,
+    /// Also report the time spent in Lean for each request environment, and in total
+    #[arg(long, conflicts_with_all = ["print_smtlib"])]
+    pub(crate) timings: bool,
+}
+
+#[derive(Args, Clone, Debug, Serialize)]
+#[clap(next_help_heading = "Solver Options")]
+pub(crate) struct SolverArgs {
+    /// Path to the CVC5 executable used by solver-backed checks [default: $CVC5]
+    #[arg(long, value_name = "PATH", global = true)]
+    pub(crate) solver: Option<PathBuf>,
+    /// Give up on each solver query after this many seconds, reporting it as unknown
+    #[arg(long, value_name = "SECONDS", global = true)]
+    pub(crate) timeout: Option<NonZeroU64>,
+    /// Limit the memory of each solver process to this many megabytes
+    #[arg(long, value_name = "MB", global = true)]
+    pub(crate) memory_limit: Option<NonZeroU64>,
 }
 
 pub(crate) enum ModeEnum {
@@ -129,6 +241,63 @@ pub(crate) struct RequestArgs {
     request_file: Option<PathBuf>,
 }
 
+#[derive(Args, Clone, Debug, Serialize)]
+#[clap(next_help_heading = "Partial Request Arguments")]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct PartialRequestArgs {
+    /// The requested principal
+    #[arg(
+        long,
+        value_name = "PRINCIPAL_NAME",
+        required_unless_present = "principal_type"
+    )]
+    pub(crate) principal: Option<String>,
+    /// The type of the requested principal, leaving its id unknown
+    #[arg(long, value_name = "PRINCIPAL_TYPE", conflicts_with = "principal")]
+    pub(crate) principal_type: Option<String>,
+    /// The requested action
+    #[arg(long, value_name = "ACTION_ID", required = true)]
+    pub(crate) action: String,
+    /// The requested resource
+    #[arg(
+        long,
+        value_name = "RESOURCE_NAME",
+        required_unless_present = "resource_type"
+    )]
+    pub(crate) resource: Option<String>,
+    /// The type of the requested resource, leaving its id unknown
+    #[arg(long, value_name = "RESOURCE_TYPE", conflicts_with = "resource")]
+    pub(crate) resource_type: Option<String>,
+    /// The context as a JSON string [default: unknown]
+    #[arg(long, value_name = "CONTEXT", conflicts_with = "context_file")]
+    pub(crate) context: Option<String>,
+    /// A file containing the context in JSON [default: unknown]
+    #[arg(long, value_name = "CONTEXT_FILE", conflicts_with = "context")]
+    pub(crate) context_file: Option<PathBuf>,
+}
+
+#[derive(Args, Clone, Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct QueryContextArgs {
+    /// The context as a JSON string
+    #[arg(long, value_name = "CONTEXT", conflicts_with = "context_file")]
+    pub(crate) context: Option<String>,
+    /// A file containing the context in JSON
+    #[arg(long, value_name = "CONTEXT_FILE", conflicts_with = "context")]
+    pub(crate) context_file: Option<PathBuf>,
+}
+
+impl QueryContextArgs {
+    /// The provided context, if any
+    pub(crate) fn to_context_arg(self) -> Option<ContextArg> {
+        match (self.context, self.context_file) {
+            (Some(json_str), _) => Some(ContextArg::FromString { json_str }),
+            (_, Some(file_name)) => Some(ContextArg::FromFile { file_name }),
+            (None, None) => None,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize)]
 pub(crate) enum ContextArg {
     FromString { json_str: String },
@@ -203,7 +372,8 @@ impl util::OpenRequestEnv {
     }
 }
 
-#[derive(ValueEnum, Clone, Copy, Debug, Serialize)]
+#[derive(ValueEnum, Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(rename_all(deserialize = "kebab-case"))]
 pub(crate) enum ValidationMode {
     Strict,
 }
@@ -216,6 +386,9 @@ pub(crate) enum EvaluationCommands {
         /// A file containing the PolicySet to authorize against
         #[clap(required = true)]
         policyset_file: PathBuf,
+        /// A JSON file of template-link instantiations to link the templates of the PolicySet with
+        #[clap(long = "links", value_name = "FILE")]
+        links_file: Option<PathBuf>,
         /// A file containing the entities relevant for authorization
         #[clap(required = true)]
         entities_file: PathBuf,
@@ -223,6 +396,154 @@ pub(crate) enum EvaluationCommands {
         schema_file: Option<PathBuf>,
         #[clap(flatten)]
         req_args: RequestArgs,
+        /// Whether to output the results in .json format
+        #[clap(long, short, action=ArgAction::SetTrue)]
+        json_output: bool,
+        /// Also report the time spent in Lean authorizing the Request
+        #[clap(long, action = ArgAction::SetTrue)]
+        timings: bool,
+    },
+    /// Partially authorize a Request whose principal/resource ids or context may be unknown
+    PartialAuthorize {
+        /// A file containing the PolicySet to authorize against
+        #[clap(required = true)]
+        policyset_file: PathBuf,
+        /// A JSON file of template-link instantiations to link the templates of the PolicySet with
+        #[clap(long = "links", value_name = "FILE")]
+        links_file: Option<PathBuf>,
+        /// A file containing the partial entities relevant for authorization
+        #[clap(required = true)]
+        entities_file: PathBuf,
+        /// A file containing the Schema used for type-aware partial evaluation
+        #[clap(required = true)]
+        schema_file: PathBuf,
+        #[clap(flatten)]
+        req_args: PartialRequestArgs,
+    },
+    /// List the principals of a given type that can perform an action on a resource
+    QueryPrincipals {
+        /// A file containing the PolicySet to authorize against
+        #[clap(required = true)]
+        policyset_file: PathBuf,
+        /// A JSON file of template-link instantiations to link the templates of the PolicySet with
+        #[clap(long = "links", value_name = "FILE")]
+        links_file: Option<PathBuf>,
+        /// A file containing the entities to find principals in
+        #[clap(required = true)]
+        entities_file: PathBuf,
+        /// A file containing the Schema
+        #[clap(required = true)]
+        schema_file: PathBuf,
+        /// The type of the principals to find
+        #[arg(long, value_name = "PRINCIPAL_TYPE")]
+        principal_type: String,
+        /// The requested action
+        #[arg(long, value_name = "ACTION_ID")]
+        action: String,
+        /// The requested resource
+        #[arg(long, value_name = "RESOURCE_NAME")]
+        resource: String,
+        #[clap(flatten)]
+        context: QueryContextArgs,
+    },
+    /// List the resources of a given type that a principal can perform an action on
+    QueryResources {
+        /// A file containing the PolicySet to authorize against
+        #[clap(required = true)]
+        policyset_file: PathBuf,
+        /// A JSON file of template-link instantiations to link the templates of the PolicySet with
+        #[clap(long = "links", value_name = "FILE")]
+        links_file: Option<PathBuf>,
+        /// A file containing the entities to find resources in
+        #[clap(required = true)]
+        entities_file: PathBuf,
+        /// A file containing the Schema
+        #[clap(required = true)]
+        schema_file: PathBuf,
+        /// The requested principal
+        #[arg(long, value_name = "PRINCIPAL_NAME")]
+        principal: String,
+        /// The requested action
+        #[arg(long, value_name = "ACTION_ID")]
+        action: String,
+        /// The type of the resources to find
+        #[arg(long, value_name = "RESOURCE_TYPE")]
+        resource_type: String,
+        #[clap(flatten)]
+        context: QueryContextArgs,
+    },
+    /// List the actions a principal can perform on a resource
+    QueryActions {
+        /// A file containing the PolicySet to authorize against
+        #[clap(required = true)]
+        policyset_file: PathBuf,
+        /// A JSON file of template-link instantiations to link the templates of the PolicySet with
+        #[clap(long = "links", value_name = "FILE")]
+        links_file: Option<PathBuf>,
+        /// A file containing the entities relevant for authorization
+        #[clap(required = true)]
+        entities_file: PathBuf,
+        /// A file containing the Schema
+        #[clap(required = true)]
+        schema_file: PathBuf,
+        /// The requested principal
+        #[arg(long, value_name = "PRINCIPAL_NAME")]
+        principal: String,
+        /// The requested resource
+        #[arg(long, value_name = "RESOURCE_NAME")]
+        resource: String,
+        #[clap(flatten)]
+        context: QueryContextArgs,
+    },
+    /// Authorize a JSONL stream of Requests, writing a JSONL result for each
+    BulkAuthorize {
+        /// A file containing the PolicySet to authorize against
+        #[clap(required = true)]
+        policyset_file: PathBuf,
+        /// A JSON file of template-link instantiations to link the templates of the PolicySet with
+        #[clap(long = "links", value_name = "FILE")]
+        links_file: Option<PathBuf>,
+        /// A file containing the entities relevant for authorization
+        #[clap(required = true)]
+        entities_file: PathBuf,
+        /// A file containing a Schema to parse entities and requests with
+        schema_file: Option<PathBuf>,
+        /// A JSONL file with a request (principal, action, resource, context) per line [default: stdin]
+        #[arg(long, value_name = "FILE")]
+        requests_file: Option<PathBuf>,
+        /// Write the JSONL results to this file [default: stdout]
+        #[arg(long, short, value_name = "FILE")]
+        output_file: Option<PathBuf>,
+        /// Print a histogram of the decisions, determining policies and erroring policies to stderr
+        #[arg(long)]
+        summary: bool,
+        /// Also report the time spent in Lean authorizing each Request, and in total
+        #[arg(long)]
+        timings: bool,
+    },
+    /// Authorize Requests using batched evaluation, loading entities lazily from an entity store
+    BatchedAuthorize {
+        /// A file containing the PolicySet to authorize against
+        #[clap(required = true)]
+        policyset_file: PathBuf,
+        /// A JSON file of template-link instantiations to link the templates of the PolicySet with
+        #[clap(long = "links", value_name = "FILE")]
+        links_file: Option<PathBuf>,
+        /// A file containing the Schema to parse entities and requests with
+        #[clap(required = true)]
+        schema_file: PathBuf,
+        /// A directory of JSON entity files, or a JSONL file with an entity per line
+        #[arg(long, value_name = "PATH")]
+        entity_store: PathBuf,
+        /// A JSONL file with a request (principal, action, resource, context) per line
+        #[arg(long, value_name = "FILE")]
+        requests_file: PathBuf,
+        /// The maximum number of batched evaluation iterations (entity loader round-trips)
+        #[arg(long, value_name = "N", default_value = "10")]
+        iterations: u32,
+        /// Whether to output the results in .json format
+        #[clap(long, short, action=ArgAction::SetTrue)]
+        json_output: bool,
     },
     /// Evaluate a Cedar Expression
     Evaluate {
@@ -239,6 +560,12 @@ pub(crate) enum EvaluationCommands {
         expected_expr_file: Option<PathBuf>,
         #[clap(flatten)]
         req_args: RequestArgs,
+        /// Whether to output the results in .json format
+        #[clap(long, short, action=ArgAction::SetTrue)]
+        json_output: bool,
+        /// Also report the time spent in Lean evaluating the expression (and comparing it with the expected output)
+        #[clap(long, action = ArgAction::SetTrue)]
+        timings: bool,
     },
 }
 
@@ -250,24 +577,42 @@ pub(crate) enum ValidationCommands {
         /// A file containing the PolicySet to validate
         #[clap(required = true)]
         policyset_file: PathBuf,
+        /// A JSON file of template-link instantiations to link the templates of the PolicySet with
+        #[clap(long = "links", value_name = "FILE")]
+        links_file: Option<PathBuf>,
         /// A file containing the Schema to validate against
         #[clap(required = true)]
         schema_file: PathBuf,
         /// Whether to use strict or permissive validation
         #[clap(default_value = "strict")]
         validation_mode: ValidationMode,
+        /// Whether to output the results in .json format
+        #[clap(long, short, action=ArgAction::SetTrue)]
+        json_output: bool,
+        /// Also report the time spent in Lean validating the PolicySet
+        #[clap(long, action = ArgAction::SetTrue)]
+        timings: bool,
     },
     /// Validate a PolicySet against a Schema using level-based validation
     Level {
         /// A file containing the PolicySet to validate
         #[clap(required = true)]
         policyset_file: PathBuf,
+        /// A JSON file of template-link instantiations to link the templates of the PolicySet with
+        #[clap(long = "links", value_name = "FILE")]
+        links_file: Option<PathBuf>,
         /// A file containing the Schema to validate against
         #[clap(required = true)]
         schema_file: PathBuf,
         /// The level to validate at
         #[clap(default_value = "0")]
         level: i32,
+        /// Whether to output the results in .json format
+        #[clap(long, short, action=ArgAction::SetTrue)]
+        json_output: bool,
+        /// Also report the time spent in Lean validating the PolicySet
+        #[clap(long, action = ArgAction::SetTrue)]
+        timings: bool,
     },
     /// Validate a Request against a Schema
     Request {
@@ -276,8 +621,79 @@ pub(crate) enum ValidationCommands {
         schema_file: PathBuf,
         #[clap(flatten)]
         req_args: RequestArgs,
+        /// Whether to output the results in .json format
+        #[clap(long, short, action=ArgAction::SetTrue)]
+        json_output: bool,
+        /// Also report the time spent in Lean validating the Request
+        #[clap(long, action = ArgAction::SetTrue)]
+        timings: bool,
     },
     /// Validate Entities against a Schema
+    Entities {
+        /// A file containing the Schema to validate against
+        #[clap(required = true)]
+        schema_file: PathBuf,
+        /// A file containing the Entities to validate
+        #[clap(required = true)]
+        entities_file: PathBuf,
+        /// Whether to output the results in .json format
+        #[clap(long, short, action=ArgAction::SetTrue)]
+        json_output: bool,
+        /// Also report the time spent in Lean validating the Entities
+        #[clap(long, action = ArgAction::SetTrue)]
+        timings: bool,
+    },
+}
+
+#[derive(Clone, Debug, Serialize, Subcommand)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum DiffCommands {
+    /// Compare Rust and Lean authorization of a Request
+    Authorize {
+        /// A file containing the PolicySet to authorize against
+        #[clap(required = true)]
+        policyset_file: PathBuf,
+        /// A file containing the entities relevant for authorization
+        #[clap(required = true)]
+        entities_file: PathBuf,
+        /// A file containing a Schema to parse entities with
+        schema_file: Option<PathBuf>,
+        #[clap(flatten)]
+        req_args: RequestArgs,
+    },
+    /// Compare Rust and Lean validation of a PolicySet against a Schema
+    PolicySet {
+        /// A file containing the PolicySet to validate
+        #[clap(required = true)]
+        policyset_file: PathBuf,
+        /// A file containing the Schema to validate against
+        #[clap(required = true)]
+        schema_file: PathBuf,
+        /// Whether to use strict or permissive validation
+        #[clap(default_value = "strict")]
+        validation_mode: ValidationMode,
+    },
+    /// Compare Rust and Lean level-based validation of a PolicySet against a Schema
+    Level {
+        /// A file containing the PolicySet to validate
+        #[clap(required = true)]
+        policyset_file: PathBuf,
+        /// A file containing the Schema to validate against
+        #[clap(required = true)]
+        schema_file: PathBuf,
+        /// The level to validate at
+        #[clap(default_value = "0")]
+        level: i32,
+    },
+    /// Compare Rust and Lean validation of a Request against a Schema
+    Request {
+        /// A file containing the Schema to validate against
+        #[clap(required = true)]
+        schema_file: PathBuf,
+        #[clap(flatten)]
+        req_args: RequestArgs,
+    },
+    /// Compare Rust and Lean validation of Entities against a Schema
     Entities {
         /// A file containing the Schema to validate against
         #[clap(required = true)]
@@ -300,6 +716,30 @@ pub(crate) enum SymCCCommands {
         mode: Mode,
         #[clap(flatten)]
         req_env: RequestEnvArgs,
+        #[clap(flatten)]
+        cex: CexArgs,
+    },
+    /// Check if the provided Policy matches all authorization requests
+    CheckAlwaysMatches {
+        #[clap(flatten)]
+        args: PolicyAnalysisArgs,
+        #[clap(flatten)]
+        mode: Mode,
+        #[clap(flatten)]
+        req_env: RequestEnvArgs,
+        #[clap(flatten)]
+        cex: CexArgs,
+    },
+    /// Check if the provided Policy matches no authorization requests
+    CheckNeverMatches {
+        #[clap(flatten)]
+        args: PolicyAnalysisArgs,
+        #[clap(flatten)]
+        mode: Mode,
+        #[clap(flatten)]
+        req_env: RequestEnvArgs,
+        #[clap(flatten)]
+        cex: CexArgs,
     },
     /// Check if the provided PolicySet allows all authorization requests
     CheckAlwaysAllows {
@@ -309,6 +749,8 @@ pub(crate) enum SymCCCommands {
         mode: Mode,
         #[clap(flatten)]
         req_env: RequestEnvArgs,
+        #[clap(flatten)]
+        cex: CexArgs,
     },
     /// Check if the provided PolicySet denies all authorization requests
     CheckAlwaysDenies {
@@ -318,6 +760,8 @@ pub(crate) enum SymCCCommands {
         mode: Mode,
         #[clap(flatten)]
         req_env: RequestEnvArgs,
+        #[clap(flatten)]
+        cex: CexArgs,
     },
     /// Check if the two PolicySets are equivalent
     CheckEquivalent {
@@ -327,6 +771,8 @@ pub(crate) enum SymCCCommands {
         mode: Mode,
         #[clap(flatten)]
         req_env: RequestEnvArgs,
+        #[clap(flatten)]
+        cex: CexArgs,
     },
     /// Check if every request allowed by `pset1` is also allowed by `pset2`
     CheckImplies {
@@ -336,6 +782,8 @@ pub(crate) enum SymCCCommands {
         mode: Mode,
         #[clap(flatten)]
         req_env: RequestEnvArgs,
+        #[clap(flatten)]
+        cex: CexArgs,
     },
     /// Check if two PolicySets are disjoint (there is no authorization request that both PolicySets allow)
     CheckDisjoint {
@@ -345,6 +793,52 @@ pub(crate) enum SymCCCommands {
         mode: Mode,
         #[clap(flatten)]
         req_env: RequestEnvArgs,
+        #[clap(flatten)]
+        cex: CexArgs,
+    },
+    /// Check if every request allowed by the PolicySet satisfies a property (a Cedar boolean expression)
+    CheckProperty {
+        #[clap(flatten)]
+        args: PropertyAnalysisArgs,
+        #[clap(flatten)]
+        mode: Mode,
+        #[clap(flatten)]
+        req_env: RequestEnvArgs,
+        #[clap(flatten)]
+        cex: CexArgs,
+    },
+    /// Check if two Policies match the same authorization requests
+    CheckMatchesEquivalent {
+        #[clap(flatten)]
+        args: ComparePolicyAnalysisArgs,
+        #[clap(flatten)]
+        mode: Mode,
+        #[clap(flatten)]
+        req_env: RequestEnvArgs,
+        #[clap(flatten)]
+        cex: CexArgs,
+    },
+    /// Check if every authorization request matched by `policy1` is also matched by `policy2`
+    CheckMatchesImplies {
+        #[clap(flatten)]
+        args: ComparePolicyAnalysisArgs,
+        #[clap(flatten)]
+        mode: Mode,
+        #[clap(flatten)]
+        req_env: RequestEnvArgs,
+        #[clap(flatten)]
+        cex: CexArgs,
+    },
+    /// Check if two Policies are disjoint (there is no authorization request that both Policies match)
+    CheckMatchesDisjoint {
+        #[clap(flatten)]
+        args: ComparePolicyAnalysisArgs,
+        #[clap(flatten)]
+        mode: Mode,
+        #[clap(flatten)]
+        req_env: RequestEnvArgs,
+        #[clap(flatten)]
+        cex: CexArgs,
     },
 }
 
@@ -355,11 +849,47 @@ pub(crate) enum AnalysisCommands {
     Policies {
         #[clap(flatten)]
         args: PolicySetAnalysisArgs,
+        /// Number of worker threads (each running its own Lean runtime) to distribute the analysis over
+        #[clap(long, value_name = "N", default_value = "1")]
+        jobs: NonZeroUsize,
+        /// Output the findings as a SARIF 2.1.0 log, located in the PolicySet file
+        #[clap(long, action = ArgAction::SetTrue, conflicts_with = "json_output")]
+        sarif: bool,
+        /// Also report policies that may error, with an example environment for each. Off by default,
+        /// as it takes up to two more solver queries per policy and request environment
+        #[clap(long, action = ArgAction::SetTrue)]
+        check_errors: bool,
+        /// Also report the time spent in Lean for each policy and pair of policies, and in total
+        #[clap(long, action = ArgAction::SetTrue, conflicts_with = "sarif")]
+        timings: bool,
+        #[clap(flatten)]
+        req_env: RequestEnvArgs,
+    },
+    /// Remove redundant, shadowed and overridden policies from a PolicySet without changing its meaning
+    Minimize {
+        #[clap(flatten)]
+        args: PolicySetAnalysisArgs,
+        /// File to write the minimized PolicySet to
+        #[clap(long, short, value_name = "FILE")]
+        output_file: PathBuf,
+        /// Also report the time spent in Lean analyzing each policy and checking each removal, and in total
+        #[clap(long, action = ArgAction::SetTrue)]
+        timings: bool,
+        #[clap(flatten)]
+        req_env: RequestEnvArgs,
     },
     /// Compare two PolicySets
     Compare {
         #[clap(flatten)]
         args: ComparePolicySetAnalysisArgs,
+        /// Also report which added, removed or changed policies (matched by id) cause the differences
+        #[clap(long, action = ArgAction::SetTrue)]
+        attribute: bool,
+        /// Also report the time spent in Lean for each request environment, and in total
+        #[clap(long, action = ArgAction::SetTrue)]
+        timings: bool,
+        #[clap(flatten)]
+        req_env: RequestEnvArgs,
     },
 }
 
@@ -381,11 +911,32 @@ pub(crate) enum Command {
         #[clap(subcommand)]
         command: ValidationCommands,
     },
+    /// Compare Rust `cedar-policy` against the Lean spec on the given inputs
+    Diff {
+        #[clap(subcommand)]
+        command: DiffCommands,
+    },
     /// Run the Cedar Symbolic Compiler
     Symcc {
         #[clap(subcommand)]
         command: SymCCCommands,
     },
+    /// Serve the evaluate, validate, symcc and analyze commands as JSON-RPC methods, reading
+    /// requests from stdin (one per line)
+    Serve {
+        /// Serve requests from connections to a Unix socket bound at this path instead of stdin
+        #[clap(long, value_name = "PATH")]
+        socket: Option<PathBuf>,
+    },
+    /// Run a suite of symbolic checks described in a TOML (or JSON) file, reporting which checks
+    /// pass and exiting with an error if any fail
+    Verify {
+        /// File containing the verification suite
+        suite_file: PathBuf,
+        /// Also write the results as a JUnit XML report to this file
+        #[clap(long, value_name = "FILE")]
+        junit: Option<PathBuf>,
+    },
 }
 
 /// Command Line Interface for Cedar Lean
@@ -394,4 +945,6 @@ pub(crate) enum Command {
 pub struct CliArgs {
     #[clap(subcommand)]
     pub(crate) command: Command,
+    #[clap(flatten)]
+    pub(crate) solver: SolverArgs,
 }
@@ -15,15 +15,20 @@
  */
 use crate::analysis;
 use crate::cli_enums::{
-    AnalysisCommands, CliArgs, Command, EvaluationCommands, ModeEnum, RequestArgsEnum,
-    SymCCCommands, ValidationCommands,
+    AnalysisCommands, CliArgs, Command, ContextArg, DiffCommands, EvaluationCommands, ModeEnum,
+    RequestArgsEnum, SymCCCommands, ValidationCommands,
 };
-use crate::err::ExecError;
+use crate::diff;
+use crate::err::{EntityType, ExecError, RequestElement};
 use crate::evaluation;
+use crate::serve;
+use crate::solver;
 use crate::symcc;
 use crate::util;
 use crate::util::OpenRequestEnv;
 use crate::validation;
+use crate::verify;
+use cedar_lean_ffi::CedarLeanFfi;
 
 impl EvaluationCommands {
     /// Execute the task described by the evaluation command
@@ -31,17 +36,174 @@ impl EvaluationCommands {
         match self {
             Self::Authorize {
                 policyset_file,
+                links_file,
                 entities_file,
                 schema_file,
                 req_args,
+                json_output,
+                timings,
             } => {
-                let policyset = util::parse_policyset(&policyset_file)?;
+                let policyset = util::parse_linked_policyset(&policyset_file, links_file.as_ref())?;
                 let schema = schema_file
                     .map(|schema_file| util::parse_schema(&schema_file))
                     .transpose()?;
                 let request = RequestArgsEnum::from(req_args).parse(schema.as_ref())?;
                 let entities = util::parse_entities(&entities_file, schema.as_ref())?;
-                evaluation::check_is_authorized(&policyset, &entities, &request)
+                evaluation::check_is_authorized(
+                    &policyset,
+                    &entities,
+                    &request,
+                    json_output,
+                    timings,
+                )
+            }
+            Self::PartialAuthorize {
+                policyset_file,
+                links_file,
+                entities_file,
+                schema_file,
+                req_args,
+            } => {
+                let policyset = util::parse_linked_policyset(&policyset_file, links_file.as_ref())?;
+                let schema = util::parse_schema(&schema_file)?;
+                let request = req_args.parse(&schema)?;
+                let entities = util::parse_partial_entities(&entities_file, &schema)?;
+                evaluation::partial_authorize(&policyset, &request, &entities, &schema)
+            }
+            Self::QueryPrincipals {
+                policyset_file,
+                links_file,
+                entities_file,
+                schema_file,
+                principal_type,
+                action,
+                resource,
+                context,
+            } => {
+                let policyset = util::parse_linked_policyset(&policyset_file, links_file.as_ref())?;
+                let schema = util::parse_schema(&schema_file)?;
+                let action = util::parse_entity_uid(action, RequestElement::Action)?;
+                let query = evaluation::EntityQuery {
+                    target: evaluation::QueryTarget::Principal,
+                    entity_type: util::parse_entity_type(
+                        principal_type,
+                        EntityType::PrincipalTypeName,
+                    )?,
+                    other: util::parse_entity_uid(resource, RequestElement::Resource)?,
+                    context: context
+                        .to_context_arg()
+                        .unwrap_or(ContextArg::Default)
+                        .parse(Some((&schema, &action)))?,
+                    action,
+                };
+                let entities = util::parse_entities(&entities_file, Some(&schema))?;
+                let partial_entities = util::parse_partial_entities(&entities_file, &schema)?;
+                evaluation::query_entities(&policyset, query, &entities, &partial_entities, &schema)
+            }
+            Self::QueryResources {
+                policyset_file,
+                links_file,
+                entities_file,
+                schema_file,
+                principal,
+                action,
+                resource_type,
+                context,
+            } => {
+                let policyset = util::parse_linked_policyset(&policyset_file, links_file.as_ref())?;
+                let schema = util::parse_schema(&schema_file)?;
+                let action = util::parse_entity_uid(action, RequestElement::Action)?;
+                let query = evaluation::EntityQuery {
+                    target: evaluation::QueryTarget::Resource,
+                    entity_type: util::parse_entity_type(
+                        resource_type,
+                        EntityType::ResourceTypeName,
+                    )?,
+                    other: util::parse_entity_uid(principal, RequestElement::Principal)?,
+                    context: context
+                        .to_context_arg()
+                        .unwrap_or(ContextArg::Default)
+                        .parse(Some((&schema, &action)))?,
+                    action,
+                };
+                let entities = util::parse_entities(&entities_file, Some(&schema))?;
+                let partial_entities = util::parse_partial_entities(&entities_file, &schema)?;
+                evaluation::query_entities(&policyset, query, &entities, &partial_entities, &schema)
+            }
+            Self::QueryActions {
+                policyset_file,
+                links_file,
+                entities_file,
+                schema_file,
+                principal,
+                resource,
+                context,
+            } => {
+                let policyset = util::parse_linked_policyset(&policyset_file, links_file.as_ref())?;
+                let schema = util::parse_schema(&schema_file)?;
+                let principal = util::parse_entity_uid(principal, RequestElement::Principal)?;
+                let resource = util::parse_entity_uid(resource, RequestElement::Resource)?;
+                // the context applies to every action, so it is not parsed against any one of them
+                let context = context
+                    .to_context_arg()
+                    .map(|context| context.parse(None))
+                    .transpose()?;
+                let partial_entities = util::parse_partial_entities(&entities_file, &schema)?;
+                evaluation::query_actions(
+                    &policyset,
+                    principal,
+                    resource,
+                    context,
+                    &partial_entities,
+                    &schema,
+                )
+            }
+            Self::BulkAuthorize {
+                policyset_file,
+                links_file,
+                entities_file,
+                schema_file,
+                requests_file,
+                output_file,
+                summary,
+                timings,
+            } => {
+                let policyset = util::parse_linked_policyset(&policyset_file, links_file.as_ref())?;
+                let schema = schema_file
+                    .map(|schema_file| util::parse_schema(&schema_file))
+                    .transpose()?;
+                let entities = util::parse_entities(&entities_file, schema.as_ref())?;
+                evaluation::bulk_authorize(
+                    &policyset,
+                    &entities,
+                    schema.as_ref(),
+                    requests_file.as_ref(),
+                    output_file.as_ref(),
+                    summary,
+                    timings,
+                )
+            }
+            Self::BatchedAuthorize {
+                policyset_file,
+                links_file,
+                schema_file,
+                entity_store,
+                requests_file,
+                iterations,
+                json_output,
+            } => {
+                let policyset = util::parse_linked_policyset(&policyset_file, links_file.as_ref())?;
+                let schema = util::parse_schema(&schema_file)?;
+                let requests = util::parse_requests_jsonl(&requests_file, Some(&schema))?;
+                let mut loader = util::FileEntityLoader::new(&entity_store, &schema)?;
+                evaluation::batched_authorize(
+                    &policyset,
+                    &schema,
+                    &requests,
+                    &mut loader,
+                    iterations,
+                    json_output,
+                )
             }
             Self::Evaluate {
                 input_expr_file,
@@ -49,6 +211,8 @@ impl EvaluationCommands {
                 schema_file,
                 req_args,
                 expected_expr_file,
+                json_output,
+                timings,
             } => {
                 let input_expr = util::parse_expression(&input_expr_file)?;
                 let schema = schema_file
@@ -59,7 +223,14 @@ impl EvaluationCommands {
                 let output_expr = expected_expr_file
                     .map(|fname| util::parse_expression(&fname))
                     .transpose()?;
-                evaluation::evaluate(&input_expr, &entities, &request, output_expr.as_ref())
+                evaluation::evaluate(
+                    &input_expr,
+                    &entities,
+                    &request,
+                    output_expr.as_ref(),
+                    json_output,
+                    timings,
+                )
             }
         }
     }
@@ -71,38 +242,107 @@ impl ValidationCommands {
         match self {
             Self::PolicySet {
                 policyset_file,
+                links_file,
                 schema_file,
                 validation_mode,
+                json_output,
+                timings,
             } => {
-                let policyset = util::parse_policyset(&policyset_file)?;
+                let policyset = util::parse_linked_policyset(&policyset_file, links_file.as_ref())?;
                 let schema = util::parse_schema(&schema_file)?;
                 let validation_mode = validation_mode.to_cedar();
-                validation::validate(&policyset, &schema, &validation_mode)
+                validation::validate(&policyset, &schema, &validation_mode, json_output, timings)
             }
             Self::Level {
                 policyset_file,
+                links_file,
                 schema_file,
                 level,
+                json_output,
+                timings,
             } => {
-                let policyset = util::parse_policyset(&policyset_file)?;
+                let policyset = util::parse_linked_policyset(&policyset_file, links_file.as_ref())?;
                 let schema = util::parse_schema(&schema_file)?;
-                validation::level_validate(&policyset, &schema, level)
+                validation::level_validate(&policyset, &schema, level, json_output, timings)
             }
             Self::Request {
                 schema_file,
                 req_args,
+                json_output,
+                timings,
             } => {
                 let schema = util::parse_schema(&schema_file)?;
                 let request = RequestArgsEnum::from(req_args).parse(Some(&schema))?;
-                validation::validate_request(&schema, &request)
+                validation::validate_request(&schema, &request, json_output, timings)
             }
             Self::Entities {
                 schema_file,
                 entities_file,
+                json_output,
+                timings,
             } => {
                 let schema = util::parse_schema(&schema_file)?;
                 let entities = util::parse_entities(&entities_file, Some(&schema))?;
-                validation::validate_entities(&schema, &entities)
+                validation::validate_entities(&schema, &entities, json_output, timings)
+            }
+        }
+    }
+}
+
+impl DiffCommands {
+    /// Execute the task described by the diff command
+    fn exec(self) -> Result<(), ExecError> {
+        match self {
+            Self::Authorize {
+                policyset_file,
+                entities_file,
+                schema_file,
+                req_args,
+            } => {
+                let policyset = util::parse_policyset(&policyset_file)?;
+                let schema = schema_file
+                    .map(|schema_file| util::parse_schema(&schema_file))
+                    .transpose()?;
+                let request = RequestArgsEnum::from(req_args).parse(schema.as_ref())?;
+                let entities = util::parse_entities(&entities_file, schema.as_ref())?;
+                diff::diff_authorize(&policyset, &entities, &request)
+            }
+            Self::PolicySet {
+                policyset_file,
+                schema_file,
+                validation_mode,
+            } => {
+                let policyset = util::parse_policyset(&policyset_file)?;
+                let schema = util::parse_schema(&schema_file)?;
+                let validation_mode = validation_mode.to_cedar();
+                diff::diff_validate(&policyset, &schema, &validation_mode)
+            }
+            Self::Level {
+                policyset_file,
+                schema_file,
+                level,
+            } => {
+                let policyset = util::parse_policyset(&policyset_file)?;
+                let schema = util::parse_schema(&schema_file)?;
+                diff::diff_level_validate(&policyset, &schema, level)
+            }
+            Self::Request {
+                schema_file,
+                req_args,
+            } => {
+                let schema = util::parse_schema(&schema_file)?;
+                // Parsed without the schema, which would reject invalid requests
+                let request = RequestArgsEnum::from(req_args).parse(None)?;
+                diff::diff_validate_request(&schema, &request)
+            }
+            Self::Entities {
+                schema_file,
+                entities_file,
+            } => {
+                let schema = util::parse_schema(&schema_file)?;
+                // Parsed without the schema, which would reject invalid entities
+                let entities = util::parse_entities(&entities_file, None)?;
+                diff::diff_validate_entities(&schema, &entities)
             }
         }
     }
@@ -112,18 +352,58 @@ impl AnalysisCommands {
     /// Execute the task described by the analysis command
     fn exec(self) -> Result<(), ExecError> {
         match self {
-            Self::Policies { args } => {
-                let policyset = util::parse_policyset(&args.policyset_file)?;
+            Self::Policies {
+                args,
+                jobs,
+                sarif,
+                check_errors,
+                timings,
+                req_env,
+            } => {
+                let policyset =
+                    util::parse_linked_policyset(&args.policyset_file, args.links_file.as_ref())?;
                 let schema = util::parse_schema(&args.schema_file)?;
-                let analyzer = analysis::Analyzer::new(&schema, args.json_output)?;
-                analyzer.analyze_policyset(policyset)
+                let req_env = OpenRequestEnv::from_request_args(req_env)?;
+                let lean_ffi = CedarLeanFfi::new();
+                let mut analyzer = analysis::Analyzer::new(&lean_ffi, &schema, args.json_output)?;
+                analyzer.set_jobs(jobs);
+                analyzer.set_sarif_output(sarif.then(|| args.policyset_file.clone()));
+                analyzer.set_check_errors(check_errors);
+                analyzer.set_timings(timings);
+                analyzer.analyze_policyset(policyset, &req_env)
             }
-            Self::Compare { args } => {
-                let pset1 = util::parse_policyset(&args.pset1_file)?;
-                let pset2 = util::parse_policyset(&args.pset2_file)?;
+            Self::Minimize {
+                args,
+                output_file,
+                timings,
+                req_env,
+            } => {
+                let policyset =
+                    util::parse_linked_policyset(&args.policyset_file, args.links_file.as_ref())?;
                 let schema = util::parse_schema(&args.schema_file)?;
-                let analyzer = analysis::Analyzer::new(&schema, args.json_output)?;
-                analyzer.compare_policysets(pset1, pset2)
+                let req_env = OpenRequestEnv::from_request_args(req_env)?;
+                let lean_ffi = CedarLeanFfi::new();
+                let mut analyzer = analysis::Analyzer::new(&lean_ffi, &schema, args.json_output)?;
+                analyzer.set_timings(timings);
+                analyzer.minimize_policyset(policyset, &req_env, &output_file)
+            }
+            Self::Compare {
+                args,
+                attribute,
+                timings,
+                req_env,
+            } => {
+                let pset1 =
+                    util::parse_linked_policyset(&args.pset1_file, args.links_file.as_ref())?;
+                let pset2 =
+                    util::parse_linked_policyset(&args.pset2_file, args.links_file.as_ref())?;
+                let schema = util::parse_schema(&args.schema_file)?;
+                let req_env = OpenRequestEnv::from_request_args(req_env)?;
+                let lean_ffi = CedarLeanFfi::new();
+                let mut analyzer = analysis::Analyzer::new(&lean_ffi, &schema, args.json_output)?;
+                analyzer.set_attribute_differences(attribute);
+                analyzer.set_timings(timings);
+                analyzer.compare_policysets(pset1, pset2, &req_env)
             }
         }
     }
@@ -137,30 +417,77 @@ impl SymCCCommands {
                 args,
                 mode,
                 req_env,
+                cex,
             } => {
                 let policy = util::parse_policy(&args.policy_file)?;
                 let schema = util::parse_schema(&args.schema_file)?;
                 let req_env = OpenRequestEnv::from_request_args(req_env)?;
+                let opts =
+                    cex.check_options(&schema, &args.schema_file, args.json_output, mode.timings);
                 match ModeEnum::from(mode) {
                     ModeEnum::RunAnalysis => {
-                        symcc::run_check_never_errors(policy, schema, &req_env)
+                        symcc::run_check_never_errors(policy, schema, &req_env, &opts)
                     }
                     ModeEnum::PrintSMTLib => {
                         symcc::print_check_never_errors(policy, schema, &req_env)
                     }
                 }
             }
+            Self::CheckAlwaysMatches {
+                args,
+                mode,
+                req_env,
+                cex,
+            } => {
+                let policy = util::parse_policy(&args.policy_file)?;
+                let schema = util::parse_schema(&args.schema_file)?;
+                let req_env = OpenRequestEnv::from_request_args(req_env)?;
+                let opts =
+                    cex.check_options(&schema, &args.schema_file, args.json_output, mode.timings);
+                match ModeEnum::from(mode) {
+                    ModeEnum::RunAnalysis => {
+                        symcc::run_check_always_matches(policy, schema, &req_env, &opts)
+                    }
+                    ModeEnum::PrintSMTLib => {
+                        symcc::print_check_always_matches(policy, schema, &req_env)
+                    }
+                }
+            }
+            Self::CheckNeverMatches {
+                args,
+                mode,
+                req_env,
+                cex,
+            } => {
+                let policy = util::parse_policy(&args.policy_file)?;
+                let schema = util::parse_schema(&args.schema_file)?;
+                let req_env = OpenRequestEnv::from_request_args(req_env)?;
+                let opts =
+                    cex.check_options(&schema, &args.schema_file, args.json_output, mode.timings);
+                match ModeEnum::from(mode) {
+                    ModeEnum::RunAnalysis => {
+                        symcc::run_check_never_matches(policy, schema, &req_env, &opts)
+                    }
+                    ModeEnum::PrintSMTLib => {
+                        symcc::print_check_never_matches(policy, schema, &req_env)
+                    }
+                }
+            }
             Self::CheckAlwaysAllows {
                 args,
                 mode,
                 req_env,
+                cex,
             } => {
-                let policyset = util::parse_policyset(&args.policyset_file)?;
+                let policyset =
+                    util::parse_linked_policyset(&args.policyset_file, args.links_file.as_ref())?;
                 let schema = util::parse_schema(&args.schema_file)?;
                 let req_env = OpenRequestEnv::from_request_args(req_env)?;
+                let opts =
+                    cex.check_options(&schema, &args.schema_file, args.json_output, mode.timings);
                 match ModeEnum::from(mode) {
                     ModeEnum::RunAnalysis => {
-                        symcc::run_check_always_allows(policyset, schema, &req_env)
+                        symcc::run_check_always_allows(policyset, schema, &req_env, &opts)
                     }
                     ModeEnum::PrintSMTLib => {
                         symcc::print_check_always_allows(policyset, schema, &req_env)
@@ -171,13 +498,17 @@ impl SymCCCommands {
                 args,
                 mode,
                 req_env,
+                cex,
             } => {
-                let policyset = util::parse_policyset(&args.policyset_file)?;
+                let policyset =
+                    util::parse_linked_policyset(&args.policyset_file, args.links_file.as_ref())?;
                 let schema = util::parse_schema(&args.schema_file)?;
                 let req_env = OpenRequestEnv::from_request_args(req_env)?;
+                let opts =
+                    cex.check_options(&schema, &args.schema_file, args.json_output, mode.timings);
                 match ModeEnum::from(mode) {
                     ModeEnum::RunAnalysis => {
-                        symcc::run_check_always_denies(policyset, schema, &req_env)
+                        symcc::run_check_always_denies(policyset, schema, &req_env, &opts)
                     }
                     ModeEnum::PrintSMTLib => {
                         symcc::print_check_always_denies(policyset, schema, &req_env)
@@ -188,14 +519,19 @@ impl SymCCCommands {
                 args,
                 mode,
                 req_env,
+                cex,
             } => {
-                let pset1 = util::parse_policyset(&args.pset1_file)?;
-                let pset2 = util::parse_policyset(&args.pset2_file)?;
+                let pset1 =
+                    util::parse_linked_policyset(&args.pset1_file, args.links_file.as_ref())?;
+                let pset2 =
+                    util::parse_linked_policyset(&args.pset2_file, args.links_file.as_ref())?;
                 let schema = util::parse_schema(&args.schema_file)?;
                 let req_env = OpenRequestEnv::from_request_args(req_env)?;
+                let opts =
+                    cex.check_options(&schema, &args.schema_file, args.json_output, mode.timings);
                 match ModeEnum::from(mode) {
                     ModeEnum::RunAnalysis => {
-                        symcc::run_check_equivalent(pset1, pset2, schema, &req_env)
+                        symcc::run_check_equivalent(pset1, pset2, schema, &req_env, &opts)
                     }
                     ModeEnum::PrintSMTLib => {
                         symcc::print_check_equivalent(pset1, pset2, schema, &req_env)
@@ -206,14 +542,19 @@ impl SymCCCommands {
                 args,
                 mode,
                 req_env,
+                cex,
             } => {
-                let pset1 = util::parse_policyset(&args.pset1_file)?;
-                let pset2 = util::parse_policyset(&args.pset2_file)?;
+                let pset1 =
+                    util::parse_linked_policyset(&args.pset1_file, args.links_file.as_ref())?;
+                let pset2 =
+                    util::parse_linked_policyset(&args.pset2_file, args.links_file.as_ref())?;
                 let schema = util::parse_schema(&args.schema_file)?;
                 let req_env = OpenRequestEnv::from_request_args(req_env)?;
+                let opts =
+                    cex.check_options(&schema, &args.schema_file, args.json_output, mode.timings);
                 match ModeEnum::from(mode) {
                     ModeEnum::RunAnalysis => {
-                        symcc::run_check_implies(pset1, pset2, schema, &req_env)
+                        symcc::run_check_implies(pset1, pset2, schema, &req_env, &opts)
                     }
                     ModeEnum::PrintSMTLib => {
                         symcc::print_check_implies(pset1, pset2, schema, &req_env)
@@ -224,32 +565,135 @@ impl SymCCCommands {
                 args,
                 mode,
                 req_env,
+                cex,
             } => {
-                let pset1 = util::parse_policyset(&args.pset1_file)?;
-                let pset2 = util::parse_policyset(&args.pset2_file)?;
+                let pset1 =
+                    util::parse_linked_policyset(&args.pset1_file, args.links_file.as_ref())?;
+                let pset2 =
+                    util::parse_linked_policyset(&args.pset2_file, args.links_file.as_ref())?;
                 let schema = util::parse_schema(&args.schema_file)?;
                 let req_env = OpenRequestEnv::from_request_args(req_env)?;
+                let opts =
+                    cex.check_options(&schema, &args.schema_file, args.json_output, mode.timings);
                 match ModeEnum::from(mode) {
                     ModeEnum::RunAnalysis => {
-                        symcc::run_check_disjoint(pset1, pset2, schema, &req_env)
+                        symcc::run_check_disjoint(pset1, pset2, schema, &req_env, &opts)
                     }
                     ModeEnum::PrintSMTLib => {
                         symcc::print_check_disjoint(pset1, pset2, schema, &req_env)
                     }
                 }
             }
+            Self::CheckProperty {
+                args,
+                mode,
+                req_env,
+                cex,
+            } => {
+                let policyset =
+                    util::parse_linked_policyset(&args.policyset_file, args.links_file.as_ref())?;
+                let property = util::parse_expression(&args.property_file)?;
+                let schema = util::parse_schema(&args.schema_file)?;
+                let req_env = OpenRequestEnv::from_request_args(req_env)?;
+                let opts =
+                    cex.check_options(&schema, &args.schema_file, args.json_output, mode.timings);
+                match ModeEnum::from(mode) {
+                    ModeEnum::RunAnalysis => {
+                        symcc::run_check_property(policyset, property, schema, &req_env, &opts)
+                    }
+                    ModeEnum::PrintSMTLib => {
+                        symcc::print_check_property(policyset, property, schema, &req_env)
+                    }
+                }
+            }
+            Self::CheckMatchesEquivalent {
+                args,
+                mode,
+                req_env,
+                cex,
+            } => {
+                let policy1 =
+                    util::parse_policy_with_id(&args.policy1_file, args.policy_id1.as_deref())?;
+                let policy2 =
+                    util::parse_policy_with_id(&args.policy2_file, args.policy_id2.as_deref())?;
+                let schema = util::parse_schema(&args.schema_file)?;
+                let req_env = OpenRequestEnv::from_request_args(req_env)?;
+                let opts =
+                    cex.check_options(&schema, &args.schema_file, args.json_output, mode.timings);
+                match ModeEnum::from(mode) {
+                    ModeEnum::RunAnalysis => symcc::run_check_matches_equivalent(
+                        policy1, policy2, schema, &req_env, &opts,
+                    ),
+                    ModeEnum::PrintSMTLib => {
+                        symcc::print_check_matches_equivalent(policy1, policy2, schema, &req_env)
+                    }
+                }
+            }
+            Self::CheckMatchesImplies {
+                args,
+                mode,
+                req_env,
+                cex,
+            } => {
+                let policy1 =
+                    util::parse_policy_with_id(&args.policy1_file, args.policy_id1.as_deref())?;
+                let policy2 =
+                    util::parse_policy_with_id(&args.policy2_file, args.policy_id2.as_deref())?;
+                let schema = util::parse_schema(&args.schema_file)?;
+                let req_env = OpenRequestEnv::from_request_args(req_env)?;
+                let opts =
+                    cex.check_options(&schema, &args.schema_file, args.json_output, mode.timings);
+                match ModeEnum::from(mode) {
+                    ModeEnum::RunAnalysis => {
+                        symcc::run_check_matches_implies(policy1, policy2, schema, &req_env, &opts)
+                    }
+                    ModeEnum::PrintSMTLib => {
+                        symcc::print_check_matches_implies(policy1, policy2, schema, &req_env)
+                    }
+                }
+            }
+            Self::CheckMatchesDisjoint {
+                args,
+                mode,
+                req_env,
+                cex,
+            } => {
+                let policy1 =
+                    util::parse_policy_with_id(&args.policy1_file, args.policy_id1.as_deref())?;
+                let policy2 =
+                    util::parse_policy_with_id(&args.policy2_file, args.policy_id2.as_deref())?;
+                let schema = util::parse_schema(&args.schema_file)?;
+                let req_env = OpenRequestEnv::from_request_args(req_env)?;
+                let opts =
+                    cex.check_options(&schema, &args.schema_file, args.json_output, mode.timings);
+                match ModeEnum::from(mode) {
+                    ModeEnum::RunAnalysis => {
+                        symcc::run_check_matches_disjoint(policy1, policy2, schema, &req_env, &opts)
+                    }
+                    ModeEnum::PrintSMTLib => {
+                        symcc::print_check_matches_disjoint(policy1, policy2, schema, &req_env)
+                    }
+                }
+            }
         }
     }
 }
 
 impl CliArgs {
-    /// Execute the task described by the command-line arguments
+    /// Execute the task described by the command-line arguments. Must not be called while other
+    /// threads are running, as it may set the `CVC5` environment variable.
     pub fn exec(self) -> Result<(), ExecError> {
+        // SAFETY: nothing has spawned a thread yet; the Lean runtime, `--jobs` workers and
+        // `serve` connections are all started by the commands below
+        let _solver_wrapper = unsafe { solver::configure_solver(&self.solver)? };
         match self.command {
             Command::Analyze { command } => command.exec(),
             Command::Evaluate { command } => command.exec(),
             Command::Validate { command } => command.exec(),
+            Command::Diff { command } => command.exec(),
             Command::Symcc { command } => command.exec(),
+            Command::Serve { socket } => serve::serve(socket.as_ref()),
+            Command::Verify { suite_file, junit } => verify::verify(&suite_file, junit.as_ref()),
         }
     }
 }
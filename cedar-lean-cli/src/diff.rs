@@ -0,0 +1,295 @@
+/*
+ * Copyright Cedar Contributors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use crate::err::ExecError;
+use cedar_lean_ffi::{CedarLeanFfi, FfiError, ValidationResponse};
+use cedar_policy::{
+    AuthorizationError, Authorizer, Entities, PolicyId, PolicySet, Request, Schema,
+    ValidationError, ValidationMode, ValidationResult, Validator,
+};
+use itertools::Itertools;
+use prettytable::{Attr, Cell, Row, Table};
+
+/// A result computed by both Rust `cedar-policy` and the Lean spec
+struct Comparison {
+    what: &'static str,
+    rust: String,
+    lean: String,
+    /// Whether the results must agree. Results that are only reported for context (e.g., error
+    /// messages, which differ between the implementations) are not compared.
+    compared: bool,
+    /// Why a disagreement of the results is expected, for the known discrepancies that `cedar-drt`
+    /// also exempts
+    exemption: Option<&'static str>,
+}
+
+impl Comparison {
+    fn compared(what: &'static str, rust: impl ToString, lean: impl ToString) -> Self {
+        Self {
+            what,
+            rust: rust.to_string(),
+            lean: lean.to_string(),
+            compared: true,
+            exemption: None,
+        }
+    }
+
+    fn reported(what: &'static str, rust: impl ToString, lean: impl ToString) -> Self {
+        Self {
+            what,
+            rust: rust.to_string(),
+            lean: lean.to_string(),
+            compared: false,
+            exemption: None,
+        }
+    }
+
+    /// Exempt a disagreement of the results, which is expected because of `exemption`
+    fn exempted_if(mut self, exempt: bool, exemption: &'static str) -> Self {
+        if exempt && self.rust != self.lean {
+            self.exemption = Some(exemption);
+        }
+        self
+    }
+
+    fn is_discrepancy(&self) -> bool {
+        self.compared && self.exemption.is_none() && self.rust != self.lean
+    }
+}
+
+/// Print the `comparisons` side by side, flagging discrepancies, and fail if there are any
+fn print_comparisons(comparisons: &[Comparison]) -> Result<(), ExecError> {
+    let mut table = Table::new();
+    // Print a nice header
+    table.add_row(Row::new(vec![
+        Cell::new("").with_style(Attr::Bold),
+        Cell::new("Rust").with_style(Attr::Bold),
+        Cell::new("Lean").with_style(Attr::Bold),
+        Cell::new("Agree").with_style(Attr::Bold),
+    ]));
+    for comparison in comparisons.iter() {
+        let agree = if !comparison.compared {
+            String::from("-")
+        } else if let Some(exemption) = comparison.exemption {
+            format!("exempt: {exemption}")
+        } else if comparison.is_discrepancy() {
+            String::from("NO")
+        } else {
+            String::from("yes")
+        };
+        table.add_row(Row::new(vec![
+            Cell::new(comparison.what),
+            Cell::new(&comparison.rust),
+            Cell::new(&comparison.lean),
+            Cell::new(&agree),
+        ]));
+    }
+    table.printstd();
+
+    let discrepancies = comparisons.iter().filter(|c| c.is_discrepancy()).count();
+    if discrepancies == 0 {
+        println!("Rust and Lean agree");
+        Ok(())
+    } else {
+        println!("Found {discrepancies} discrepancies between Rust and Lean");
+        Err(ExecError::DiffDiscrepancies { discrepancies })
+    }
+}
+
+fn ids_or_none<'a>(pids: impl IntoIterator<Item = &'a PolicyId>) -> String {
+    let ids = pids
+        .into_iter()
+        .map(ToString::to_string)
+        .sorted()
+        .join(", ");
+    if ids.is_empty() {
+        String::from("none")
+    } else {
+        ids
+    }
+}
+
+/// Lean fails, rather than reporting a validation result, on extensions it does not support yet
+const UNKNOWN_EXTENSION: &str = "unknown extension function or type in Lean (cedar-spec#175)";
+
+/// Rust is stricter than Lean when typechecking template-linked policies
+const TEMPLATE_LINKED: &str = "Rust is stricter on template-linked policies (cedar-spec#945)";
+
+/// `lean_response`, with the Lean errors for unsupported extension functions and types reported
+/// as a failed validation
+fn lean_validation_response(
+    lean_response: Result<ValidationResponse, FfiError>,
+) -> Result<(ValidationResponse, bool), ExecError> {
+    match lean_response {
+        Ok(response) => Ok((response, false)),
+        Err(err) if is_unknown_extension(&err) => {
+            Ok((ValidationResponse::Error(err.to_string()), true))
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Whether `err` is Lean failing on an extension function or type it does not support yet
+fn is_unknown_extension(err: &FfiError) -> bool {
+    let err = err.to_string();
+    err.contains("unknown extension function") || err.contains("unknown extension type")
+}
+
+/// Compare the validation results of Rust and Lean. With `has_templates`, Lean accepting what Rust
+/// rejects is a known discrepancy, as is Lean failing on an unsupported extension.
+fn validation_comparisons(
+    rust_errors: Vec<String>,
+    lean_response: Result<ValidationResponse, FfiError>,
+    has_templates: bool,
+) -> Result<Vec<Comparison>, ExecError> {
+    let rust_valid = rust_errors.is_empty();
+    let (lean_response, unknown_extension) = lean_validation_response(lean_response)?;
+    let (lean_valid, lean_errors) = match lean_response {
+        ValidationResponse::Ok(()) => (true, String::from("none")),
+        ValidationResponse::Error(s) => (false, s),
+    };
+    let rust_errors = if rust_valid {
+        String::from("none")
+    } else {
+        rust_errors.join("\n")
+    };
+    Ok(vec![
+        Comparison::compared("Valid", rust_valid, lean_valid)
+            .exempted_if(unknown_extension, UNKNOWN_EXTENSION)
+            .exempted_if(has_templates && lean_valid, TEMPLATE_LINKED),
+        Comparison::reported("Errors", rust_errors, lean_errors),
+    ])
+}
+
+/// The errors found by the Rust validator, ignoring `InvalidActionApplication` which is never
+/// reported by Lean
+fn rust_policy_validation_errors(result: ValidationResult) -> Vec<String> {
+    result
+        .validation_errors()
+        .filter(|e| !matches!(e, ValidationError::InvalidActionApplication(_)))
+        .map(|e| format!("{}: {e}", e.policy_id()))
+        .collect()
+}
+
+/// Authorize `request` with both Rust and Lean, and print the decisions, determining policies
+/// and erroring policies side by side
+pub fn diff_authorize(
+    policyset: &PolicySet,
+    entities: &Entities,
+    request: &Request,
+) -> Result<(), ExecError> {
+    let rust_response = Authorizer::new().is_authorized(request, policyset, entities);
+    let lean_response = CedarLeanFfi::new().is_authorized(policyset, entities, request)?;
+
+    let rust_erroring = rust_response
+        .diagnostics()
+        .errors()
+        .map(|err| match err {
+            AuthorizationError::PolicyEvaluationError(err) => err.policy_id(),
+        })
+        .collect::<Vec<_>>();
+    print_comparisons(&[
+        Comparison::compared(
+            "Decision",
+            format!("{:?}", rust_response.decision()),
+            format!("{:?}", lean_response.decision()),
+        ),
+        Comparison::compared(
+            "Determining policies",
+            ids_or_none(rust_response.diagnostics().reason()),
+            ids_or_none(lean_response.determining_policies()),
+        ),
+        Comparison::compared(
+            "Erroring policies",
+            ids_or_none(rust_erroring),
+            ids_or_none(lean_response.erroring_policies()),
+        ),
+    ])
+}
+
+/// Validate `policyset` against `schema` with both Rust and Lean, and print the results side by side
+pub fn diff_validate(
+    policyset: &PolicySet,
+    schema: &Schema,
+    mode: &ValidationMode,
+) -> Result<(), ExecError> {
+    let rust_result = Validator::new(schema.clone()).validate(policyset, *mode);
+    let lean_response = CedarLeanFfi::new().validate(policyset, schema, mode);
+    print_comparisons(&validation_comparisons(
+        rust_policy_validation_errors(rust_result),
+        lean_response,
+        policyset.num_of_templates() != 0,
+    )?)
+}
+
+/// Validate `policyset` against `schema` at level `level` with both Rust and Lean, and print the
+/// results side by side. Negative levels are rejected.
+pub fn diff_level_validate(
+    policyset: &PolicySet,
+    schema: &Schema,
+    level: i32,
+) -> Result<(), ExecError> {
+    let rust_level =
+        u32::try_from(level).map_err(|_| ExecError::NegativeValidationLevel { level })?;
+    let rust_result = Validator::new(schema.clone()).validate_with_level(
+        policyset,
+        ValidationMode::Strict,
+        rust_level,
+    );
+    let lean_response = CedarLeanFfi::new().level_validate(policyset, schema, level);
+    print_comparisons(&validation_comparisons(
+        rust_policy_validation_errors(rust_result),
+        lean_response,
+        policyset.num_of_templates() != 0,
+    )?)
+}
+
+/// Validate `entities` (parsed without a schema) against `schema` with both Rust and Lean, and
+/// print the results side by side
+pub fn diff_validate_entities(schema: &Schema, entities: &Entities) -> Result<(), ExecError> {
+    let rust_errors = Entities::from_entities(entities.iter().cloned(), Some(schema))
+        .err()
+        .map(|e| e.to_string())
+        .into_iter()
+        .collect();
+    let lean_response = CedarLeanFfi::new().validate_entities(schema, entities);
+    print_comparisons(&validation_comparisons(rust_errors, lean_response, false)?)
+}
+
+/// Validate `request` (parsed without a schema) against `schema` with both Rust and Lean, and
+/// print the results side by side
+pub fn diff_validate_request(schema: &Schema, request: &Request) -> Result<(), ExecError> {
+    let rust_errors = match (
+        request.principal(),
+        request.action(),
+        request.resource(),
+        request.context(),
+    ) {
+        (Some(principal), Some(action), Some(resource), Some(context)) => Request::new(
+            principal.clone(),
+            action.clone(),
+            resource.clone(),
+            context.clone(),
+            Some(schema),
+        )
+        .err()
+        .map(|e| e.to_string())
+        .into_iter()
+        .collect(),
+        _ => vec![String::from("request has unknown components")],
+    };
+    let lean_response = CedarLeanFfi::new().validate_request(schema, request);
+    print_comparisons(&validation_comparisons(rust_errors, lean_response, false)?)
+}
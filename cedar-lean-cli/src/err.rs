@@ -20,14 +20,20 @@ use thiserror::Error;
 /// The type of struct was expected to be contained within the file
 #[derive(Clone, Debug)]
 pub enum ContentType {
+    AuthorizationResults,
     Context,
     Entities,
     Expression,
+    IntegrationTest,
+    JUnitReport,
     Policy,
     PolicySet,
     Request,
     Schema,
     SchemaJSON,
+    SolverWrapper,
+    TemplateLinks,
+    VerificationSuite,
 }
 
 /// The element of a RequestEnvironment that was involved in the error
@@ -54,13 +60,19 @@ pub enum ExecError {
     FileReadError {
         content_type: ContentType,
         file_name: PathBuf,
-        error: Box<dyn std::error::Error>,
+        error: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[error("Error writing {content_type:?} to {file_name} : {error}")]
+    FileWriteError {
+        content_type: ContentType,
+        file_name: PathBuf,
+        error: Box<dyn std::error::Error + Send + Sync>,
     },
     #[error("Error parsing {content_type:?} from {file_name} : {error}")]
     ParseError {
         content_type: ContentType,
         file_name: PathBuf,
-        error: Box<dyn std::error::Error>,
+        error: Box<dyn std::error::Error + Send + Sync>,
     },
     #[error(
         "Error while attempting to use id annotations as policy ids in while parsing {content_type:?} from {file_name}: {error}"
@@ -79,20 +91,84 @@ pub enum ExecError {
     EntityTypeError {
         entity_type: EntityType,
         input_str: String,
-        error: Box<dyn std::error::Error>,
+        error: Box<dyn std::error::Error + Send + Sync>,
     },
     #[error("Error Creating {element:?} from {input_str} : {error}")]
     RequestError {
         element: RequestElement,
         input_str: String,
-        error: Box<dyn std::error::Error>,
+        error: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[error("Policy {policy_id} not found in PolicySet from {file_name}")]
+    PolicyNotFoundError {
+        policy_id: String,
+        file_name: PathBuf,
+    },
+    #[error("Error linking template-linked policy {link_id} from {file_name} : {error}")]
+    LinkError {
+        link_id: String,
+        file_name: PathBuf,
+        error: Box<dyn std::error::Error + Send + Sync>,
     },
     #[error("Error converting Policy to a PolicySet : {error}")]
-    PolicyIntoPolicySetError { error: Box<dyn std::error::Error> },
+    PolicyIntoPolicySetError {
+        error: Box<dyn std::error::Error + Send + Sync>,
+    },
     #[error("Error during analysis : {error}")]
-    InternalAnalysisError { error: Box<dyn std::error::Error> },
+    InternalAnalysisError {
+        error: Box<dyn std::error::Error + Send + Sync>,
+    },
     #[error("Error Creating Request : {error}")]
-    RequestValidationError { error: Box<dyn std::error::Error> },
+    RequestValidationError {
+        error: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[error("Error loading entity {uid} : {error}")]
+    EntityLoadError {
+        uid: String,
+        error: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[error("Entity {uid} is defined more than once in {file_name}")]
+    DuplicateEntityError { uid: String, file_name: PathBuf },
+    #[error("Error during batched authorization : {error}")]
+    BatchedAuthorizationError {
+        error: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[error(
+        "Batched authorization of request {request} gave {rust_decision} in Rust but {lean_decision} in Lean"
+    )]
+    BatchedDecisionMismatch {
+        request: String,
+        rust_decision: String,
+        lean_decision: String,
+    },
+    #[error("{failed} of {total} requests failed batched authorization")]
+    BatchedAuthorizationFailed { failed: usize, total: usize },
+    #[error("Error answering query : {error}")]
+    QueryError {
+        error: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[error("Rust and Lean disagree on the decision for {mismatches} query candidates")]
+    QueryDecisionMismatch { mismatches: usize },
+    #[error("Error converting the evaluation result : {error}")]
+    EvaluationResultError {
+        error: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[error("Check `{check}` of kind `{kind}` requires `{field}`")]
+    SuiteCheckMissingField {
+        check: String,
+        kind: String,
+        field: &'static str,
+    },
+    #[error("{failed} of {total} checks failed")]
+    VerificationFailed { failed: usize, total: usize },
+    #[error("Error serving JSON-RPC requests : {error}")]
+    ServeError {
+        error: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[error("Rust and Lean disagree on {discrepancies} results")]
+    DiffDiscrepancies { discrepancies: usize },
+    #[error("Validation level must be non-negative, but got {level}")]
+    NegativeValidationLevel { level: i32 },
     #[error("Could not fetch actions from Schema")]
     ActionsFromSchemaError(#[from] Box<EntitiesError>),
     #[error("{principal_type} cannot {action_name} on {resource_type} in the provided Schema")]
@@ -13,19 +13,54 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
-use crate::err::ExecError;
-use cedar_lean_ffi::CedarLeanFfi;
-use cedar_policy::{Decision, Entities, Expression, PolicySet, Request};
+use crate::err::{ContentType, ExecError};
+use crate::util::{
+    self, FileEntityLoader, TimedOutputSer, TimedStep, Timings, TimingsSer, split_timed,
+};
+use cedar_lean_ffi::{
+    AuthorizationResponse, CedarLeanFfi, EvaluationError, EvaluationResponse, TpeResponse,
+};
+use cedar_policy::{
+    ActionQueryRequest, Context, Decision, Entities, EntityTypeName, EntityUid, Expression,
+    PartialEntities, PartialEntityUid, PartialRequest, PolicyId, PolicySet, PrincipalQueryRequest,
+    Request, ResourceQueryRequest, RestrictedExpression, Schema, pst,
+};
+use cedar_policy_core::batched_evaluator::err::BatchedEvalError;
 use itertools::Itertools;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::PathBuf;
+use std::time::Duration;
 
 /// Use the lean_ffi to check if the `policyset` allows the given `request`.
 pub fn check_is_authorized(
     policyset: &PolicySet,
     entities: &Entities,
     request: &Request,
+    json_output: bool,
+    timings: bool,
 ) -> Result<(), ExecError> {
     let lean_context = CedarLeanFfi::new();
-    let auth_response = lean_context.is_authorized(policyset, entities, request)?;
+    let (auth_response, duration) =
+        split_timed(lean_context.is_authorized_timed(policyset, entities, request)?);
+    let mut auth_timings = Timings::default();
+    auth_timings.push(TimedStep::Authorization, duration);
+    if json_output {
+        let result = AuthorizationResult::new(&auth_response);
+        let json = if timings {
+            serde_json::to_string_pretty(&TimedOutputSer {
+                results: result,
+                timings: TimingsSer::from(&auth_timings),
+            })
+        } else {
+            serde_json::to_string_pretty(&result)
+        }
+        .unwrap();
+        println!("{}", json);
+        return Ok(());
+    }
     match auth_response.decision() {
         Decision::Deny if auth_response.determining_policies().is_empty() => {
             println!("This request was implicitly denied as this request matched no policies")
@@ -50,32 +85,811 @@ pub fn check_is_authorized(
             auth_response.erroring_policies().iter().join(" ")
         );
     }
+    if timings {
+        println!();
+        auth_timings.print_table();
+    }
+    Ok(())
+}
+
+/// The `--json-output` of `evaluate authorize`
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct AuthorizationResult {
+    decision: String,
+    determining_policies: Vec<String>,
+    erroring_policies: Vec<String>,
+}
+
+impl AuthorizationResult {
+    fn new(auth_response: &AuthorizationResponse) -> Self {
+        Self {
+            decision: format!("{:?}", auth_response.decision()),
+            determining_policies: sorted_ids(auth_response.determining_policies()),
+            erroring_policies: sorted_ids(auth_response.erroring_policies()),
+        }
+    }
+}
+
+/// Use the lean_ffi to check if the `policyset` allows the given `request`, returning the
+/// `--json-output` of `evaluate authorize`
+pub(crate) fn authorization_result(
+    lean_context: &CedarLeanFfi,
+    policyset: &PolicySet,
+    entities: &Entities,
+    request: &Request,
+) -> Result<AuthorizationResult, ExecError> {
+    let auth_response = lean_context.is_authorized(policyset, entities, request)?;
+    Ok(AuthorizationResult::new(&auth_response))
+}
+
+fn sorted_ids(ids: &HashSet<PolicyId>) -> Vec<String> {
+    ids.iter().map(ToString::to_string).sorted().collect()
+}
+
+/// Use the lean_ffi to perform type-aware partial evaluation of the `policyset` on the partial
+/// `request` and `entities`, printing the decision if it is known and the residual policies otherwise.
+pub fn partial_authorize(
+    policyset: &PolicySet,
+    request: &PartialRequest,
+    entities: &PartialEntities,
+    schema: &Schema,
+) -> Result<(), ExecError> {
+    let lean_context = CedarLeanFfi::new();
+    let response = lean_context.is_authorized_partial(policyset, request, entities, schema)?;
+    match response.decision {
+        Some(Decision::Allow) => {
+            println!(
+                "This request is allowed as it matched the following policies: {}",
+                response.satisfied_permits.iter().sorted().join(" ")
+            )
+        }
+        Some(Decision::Deny) if response.satisfied_forbids.is_empty() => {
+            println!("This request is implicitly denied as it can match no permit policies")
+        }
+        Some(Decision::Deny) => {
+            println!(
+                "This request is denied as it matched the following policies: {}",
+                response.satisfied_forbids.iter().sorted().join(" ")
+            )
+        }
+        None => {
+            println!(
+                "The decision for this request depends on unknown values. The residual policies are:"
+            );
+            print_residual_policies(&response)?;
+        }
+    }
+    let erroring = response
+        .error_permits
+        .iter()
+        .chain(response.error_forbids.iter())
+        .sorted()
+        .join(" ");
+    if !erroring.is_empty() {
+        println!();
+        println!(
+            "The following policies did not contribute to the decision as they errored during evaluation: {erroring}"
+        );
+    }
+    Ok(())
+}
+
+/// Print, as Cedar policies, the residuals of the policies that could not be fully evaluated
+fn print_residual_policies(response: &TpeResponse) -> Result<(), ExecError> {
+    for residual in response
+        .residuals
+        .iter()
+        .filter(|r| {
+            response.residual_permits.contains(&r.id) || response.residual_forbids.contains(&r.id)
+        })
+        .sorted_by_key(|r| r.id.to_string())
+    {
+        let expr = pst::Expr::try_from(residual.residual.clone())
+            .map_err(|e| ExecError::InternalAnalysisError { error: Box::new(e) })?;
+        println!();
+        println!("@id(\"{}\")", residual.id.to_string().escape_debug());
+        println!(
+            "{}(principal, action, resource) when {{ {expr} }};",
+            residual.effect
+        );
+    }
+    Ok(())
+}
+
+/// Whether an entity query looks for principals or for resources
+#[derive(Debug, Clone, Copy)]
+pub enum QueryTarget {
+    Principal,
+    Resource,
+}
+
+impl std::fmt::Display for QueryTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Principal => write!(f, "principal"),
+            Self::Resource => write!(f, "resource"),
+        }
+    }
+}
+
+/// A query for the entities of type `entity_type` that, as the `target` of a request for `action`
+/// with `context`, are allowed together with the known `other` entity (the resource when querying
+/// principals, and the principal when querying resources)
+pub struct EntityQuery {
+    pub target: QueryTarget,
+    pub entity_type: EntityTypeName,
+    pub other: EntityUid,
+    pub action: EntityUid,
+    pub context: Context,
+}
+
+fn query_error(error: impl std::error::Error + Send + Sync + 'static) -> ExecError {
+    ExecError::QueryError {
+        error: Box::new(error),
+    }
+}
+
+/// Print a disagreement between the decision Rust `query_*` and the lean_ffi reached for `candidate`,
+/// returning the number of disagreements (0 or 1)
+fn check_candidate_decision(
+    candidate: &EntityUid,
+    rust_decision: Option<Decision>,
+    lean_decision: Option<Decision>,
+) -> usize {
+    if rust_decision == lean_decision {
+        return 0;
+    }
+    println!(
+        "Rust and Lean disagree on {candidate}: Rust decides {rust_decision:?}, Lean decides {lean_decision:?}"
+    );
+    1
+}
+
+/// List the entities of the queried type in `entities` answering the `query`, using the lean_ffi to
+/// partially authorize the request for each of them and checking its decision against Rust
+/// `query_principal`/`query_resource`. Residual policies are printed for each candidate whose decision
+/// is not known, and for the request with an unknown entity of the queried type, whose residuals
+/// describe the conditions that any other such entity must satisfy.
+pub fn query_entities(
+    policyset: &PolicySet,
+    query: EntityQuery,
+    entities: &Entities,
+    partial_entities: &PartialEntities,
+    schema: &Schema,
+) -> Result<(), ExecError> {
+    let EntityQuery {
+        target,
+        entity_type,
+        other,
+        action,
+        context,
+    } = query;
+    let rust_allowed: HashSet<EntityUid> = match target {
+        QueryTarget::Principal => {
+            let request = PrincipalQueryRequest::new(
+                entity_type.clone(),
+                action.clone(),
+                other.clone(),
+                context.clone(),
+                schema,
+            )
+            .map_err(|e| ExecError::RequestValidationError { error: Box::new(e) })?;
+            policyset
+                .query_principal(&request, entities, schema)
+                .map_err(query_error)?
+                .collect()
+        }
+        QueryTarget::Resource => {
+            let request = ResourceQueryRequest::new(
+                other.clone(),
+                action.clone(),
+                entity_type.clone(),
+                context.clone(),
+                schema,
+            )
+            .map_err(|e| ExecError::RequestValidationError { error: Box::new(e) })?;
+            policyset
+                .query_resource(&request, entities, schema)
+                .map_err(query_error)?
+                .collect()
+        }
+    };
+    // the principal and resource of a request for `target`, which is unknown if `None`
+    let partial_request = |target_uid: Option<&EntityUid>| {
+        let target_uid = match target_uid {
+            Some(uid) => PartialEntityUid::from_concrete(uid.clone()),
+            None => PartialEntityUid::new(entity_type.clone(), None),
+        };
+        let (principal, resource) = match target {
+            QueryTarget::Principal => (target_uid, PartialEntityUid::from_concrete(other.clone())),
+            QueryTarget::Resource => (PartialEntityUid::from_concrete(other.clone()), target_uid),
+        };
+        PartialRequest::new(
+            principal,
+            action.clone(),
+            resource,
+            Some(context.clone()),
+            schema,
+        )
+        .map_err(|e| ExecError::RequestValidationError { error: Box::new(e) })
+    };
+
+    let lean_context = CedarLeanFfi::new();
+    let candidates = entities
+        .iter()
+        .map(|entity| entity.uid())
+        .filter(|uid| uid.type_name() == &entity_type)
+        .sorted_by_key(|uid| uid.to_string());
+    let mut responses = Vec::new();
+    let mut mismatches = 0;
+    for candidate in candidates {
+        let response = lean_context.is_authorized_partial(
+            policyset,
+            &partial_request(Some(&candidate))?,
+            partial_entities,
+            schema,
+        )?;
+        let rust_decision = if rust_allowed.contains(&candidate) {
+            Decision::Allow
+        } else {
+            Decision::Deny
+        };
+        mismatches += check_candidate_decision(&candidate, Some(rust_decision), response.decision);
+        responses.push((candidate, response));
+    }
+
+    let allowed_str = responses
+        .iter()
+        .filter(|(_, response)| response.decision == Some(Decision::Allow))
+        .map(|(candidate, _)| candidate)
+        .join(" ");
+    match target {
+        QueryTarget::Principal if allowed_str.is_empty() => println!(
+            "No principal of type {entity_type} in the entity store can perform {action} on {other}"
+        ),
+        QueryTarget::Principal => println!(
+            "The following principals of type {entity_type} can perform {action} on {other}: {allowed_str}"
+        ),
+        QueryTarget::Resource if allowed_str.is_empty() => println!(
+            "{other} can perform {action} on no resource of type {entity_type} in the entity store"
+        ),
+        QueryTarget::Resource => println!(
+            "{other} can perform {action} on the following resources of type {entity_type}: {allowed_str}"
+        ),
+    }
+    for (candidate, response) in responses.iter().filter(|(_, r)| r.decision.is_none()) {
+        println!();
+        match target {
+            QueryTarget::Principal => println!(
+                "Whether {candidate} can perform {action} on {other} depends on the following residual policies:"
+            ),
+            QueryTarget::Resource => println!(
+                "Whether {other} can perform {action} on {candidate} depends on the following residual policies:"
+            ),
+        }
+        print_residual_policies(response)?;
+    }
+
+    let response = lean_context.is_authorized_partial(
+        policyset,
+        &partial_request(None)?,
+        partial_entities,
+        schema,
+    )?;
+    println!();
+    match response.decision {
+        Some(decision) => {
+            println!("The decision for any {target} of type {entity_type} is {decision:?}")
+        }
+        None => {
+            println!(
+                "The decision for a {target} of type {entity_type} depends on the following residual policies:"
+            );
+            print_residual_policies(&response)?;
+        }
+    }
+    if mismatches > 0 {
+        return Err(ExecError::QueryDecisionMismatch { mismatches });
+    }
+    Ok(())
+}
+
+/// List the actions that `principal` can perform on `resource` (with `context`, if known), using the
+/// lean_ffi to partially authorize the request for each action and checking its decision against Rust
+/// `query_action`. The residual policies are printed for every action whose decision depends on the
+/// unknown context or entity data.
+pub fn query_actions(
+    policyset: &PolicySet,
+    principal: EntityUid,
+    resource: EntityUid,
+    context: Option<Context>,
+    partial_entities: &PartialEntities,
+    schema: &Schema,
+) -> Result<(), ExecError> {
+    let request = ActionQueryRequest::new(
+        PartialEntityUid::from_concrete(principal.clone()),
+        PartialEntityUid::from_concrete(resource.clone()),
+        context.clone(),
+        schema.clone(),
+    )
+    .map_err(|e| ExecError::RequestValidationError { error: Box::new(e) })?;
+    let actions: Vec<(EntityUid, Option<Decision>)> = policyset
+        .query_action(&request, partial_entities)
+        .map_err(query_error)?
+        .map(|(action, decision)| (action.clone(), decision))
+        .sorted_by_key(|(action, _)| action.to_string())
+        .collect();
+
+    let lean_context = CedarLeanFfi::new();
+    let mut responses = Vec::new();
+    let mut mismatches = 0;
+    for (action, rust_decision) in actions {
+        let request = PartialRequest::new(
+            PartialEntityUid::from_concrete(principal.clone()),
+            action.clone(),
+            PartialEntityUid::from_concrete(resource.clone()),
+            context.clone(),
+            schema,
+        )
+        .map_err(|e| ExecError::RequestValidationError { error: Box::new(e) })?;
+        let response =
+            lean_context.is_authorized_partial(policyset, &request, partial_entities, schema)?;
+        mismatches += check_candidate_decision(&action, rust_decision, response.decision);
+        responses.push((action, response));
+    }
+
+    let allowed = responses
+        .iter()
+        .filter(|(_, response)| response.decision == Some(Decision::Allow))
+        .map(|(action, _)| action)
+        .join(" ");
+    if allowed.is_empty() {
+        println!("{principal} can perform no actions on {resource}");
+    } else {
+        println!("{principal} can perform the following actions on {resource}: {allowed}");
+    }
+    for (action, response) in responses.iter().filter(|(_, r)| r.decision.is_none()) {
+        println!();
+        println!(
+            "Whether {principal} can perform {action} on {resource} depends on the following residual policies:"
+        );
+        print_residual_policies(response)?;
+    }
+    if mismatches > 0 {
+        return Err(ExecError::QueryDecisionMismatch { mismatches });
+    }
+    Ok(())
+}
+
+/// The result of authorizing the request on a line of a JSONL stream
+#[derive(Debug, Clone, Serialize)]
+struct BulkAuthorizationResult {
+    /// The (1-based) line number of the request
+    line: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    decision: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    determining_policies: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    erroring_policies: Option<Vec<String>>,
+    /// Why the request could not be authorized, e.g., because it could not be parsed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    /// The time spent in Lean authorizing the request, with `--timings`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duration_ms: Option<f64>,
+}
+
+/// Counts of the decisions, and of the policies determining them or erroring, over all requests
+#[derive(Debug, Default)]
+struct BulkAuthorizationSummary {
+    decisions: BTreeMap<String, usize>,
+    determining_policies: BTreeMap<String, usize>,
+    erroring_policies: BTreeMap<String, usize>,
+}
+
+impl BulkAuthorizationSummary {
+    fn add(&mut self, result: &BulkAuthorizationResult) {
+        let decision = result.decision.as_deref().unwrap_or("Error");
+        *self.decisions.entry(decision.to_string()).or_default() += 1;
+        for pid in result.determining_policies.iter().flatten() {
+            *self.determining_policies.entry(pid.clone()).or_default() += 1;
+        }
+        for pid in result.erroring_policies.iter().flatten() {
+            *self.erroring_policies.entry(pid.clone()).or_default() += 1;
+        }
+    }
+
+    fn print(&self) {
+        for (title, histogram) in [
+            ("Decisions", &self.decisions),
+            ("Determining policies", &self.determining_policies),
+            ("Erroring policies", &self.erroring_policies),
+        ] {
+            if histogram.is_empty() {
+                continue;
+            }
+            eprintln!("{title}:");
+            for (key, count) in histogram {
+                eprintln!("  {key}: {count}");
+            }
+        }
+    }
+}
+
+/// Use the lean_ffi to authorize the request on each line of the JSONL `requests_file` (or stdin)
+/// against `policyset` and `entities`, writing a JSONL result for each to `output_file` (or stdout).
+/// With `summary`, a histogram of the decisions and policies is printed to stderr at the end. With
+/// `timings`, each result also reports the time spent in Lean authorizing its request, and the total
+/// is printed to stderr at the end.
+pub fn bulk_authorize(
+    policyset: &PolicySet,
+    entities: &Entities,
+    schema: Option<&Schema>,
+    requests_file: Option<&PathBuf>,
+    output_file: Option<&PathBuf>,
+    summary: bool,
+    timings: bool,
+) -> Result<(), ExecError> {
+    let requests_name = requests_file
+        .cloned()
+        .unwrap_or_else(|| PathBuf::from("<stdin>"));
+    let output_name = output_file
+        .cloned()
+        .unwrap_or_else(|| PathBuf::from("<stdout>"));
+    let read_error = |e: std::io::Error| ExecError::FileReadError {
+        content_type: ContentType::Request,
+        file_name: requests_name.clone(),
+        error: Box::new(e),
+    };
+    let write_error = |e: std::io::Error| ExecError::FileWriteError {
+        content_type: ContentType::AuthorizationResults,
+        file_name: output_name.clone(),
+        error: Box::new(e),
+    };
+    let requests: Box<dyn BufRead> = match requests_file {
+        Some(fname) => Box::new(BufReader::new(File::open(fname).map_err(read_error)?)),
+        None => Box::new(std::io::stdin().lock()),
+    };
+    let mut output: Box<dyn Write> = match output_file {
+        Some(fname) => Box::new(BufWriter::new(File::create(fname).map_err(write_error)?)),
+        None => Box::new(std::io::stdout().lock()),
+    };
+    let lean_context = CedarLeanFfi::new();
+    let mut totals = BulkAuthorizationSummary::default();
+    let mut total_duration = Duration::ZERO;
+    for (ind, line) in requests.lines().enumerate() {
+        let line = line.map_err(read_error)?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response =
+            util::parse_request_json(&line, &requests_name, schema).and_then(|request| {
+                Ok(split_timed(
+                    lean_context.is_authorized_timed(policyset, entities, &request)?,
+                ))
+            });
+        let result = match response {
+            Ok((response, duration)) => {
+                total_duration += duration;
+                BulkAuthorizationResult {
+                    line: ind + 1,
+                    decision: Some(format!("{:?}", response.decision())),
+                    determining_policies: Some(sorted_ids(response.determining_policies())),
+                    erroring_policies: Some(sorted_ids(response.erroring_policies())),
+                    error: None,
+                    duration_ms: timings.then(|| util::millis(duration)),
+                }
+            }
+            Err(e) => BulkAuthorizationResult {
+                line: ind + 1,
+                decision: None,
+                determining_policies: None,
+                erroring_policies: None,
+                error: Some(e.to_string()),
+                duration_ms: None,
+            },
+        };
+        totals.add(&result);
+        serde_json::to_writer(&mut output, &result)
+            .map_err(std::io::Error::from)
+            .and_then(|()| writeln!(output))
+            .map_err(write_error)?;
+    }
+    output.flush().map_err(write_error)?;
+    if summary {
+        totals.print();
+    }
+    if timings {
+        eprintln!("Total time in Lean: {:.3} ms", util::millis(total_duration));
+    }
     Ok(())
 }
 
-/// Use the lean_ffi to evaluate the input Cedar `Expression` and determine if it equivalent to
-/// the provided output Cedar `Expression` (if one was provided); otherwise, print the evaluation of the input.
+/// The outcome of batched authorization for a single request
+#[derive(Debug, Clone, Serialize)]
+struct BatchedAuthorizationResult {
+    principal: String,
+    action: String,
+    resource: String,
+    /// The decision, unless the iteration bound was too small to reach one
+    decision: Option<String>,
+    /// The number of loader round-trips needed
+    round_trips: usize,
+    insufficient_iterations: bool,
+    /// Why the request could not be authorized, e.g., because an entity could not be loaded
+    /// or the Rust and Lean decisions differ
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+fn request_component(uid: Option<&EntityUid>) -> String {
+    uid.map(ToString::to_string).unwrap_or_default()
+}
+
+/// Authorize `request` with the Rust batched evaluator and check the decision against the
+/// lean_ffi's batched authorization on the entities loaded for it
+fn batched_decision(
+    lean_context: &CedarLeanFfi,
+    policyset: &PolicySet,
+    schema: &Schema,
+    request: &Request,
+    loader: &mut FileEntityLoader<'_>,
+    iterations: u32,
+) -> Result<Option<Decision>, ExecError> {
+    let decision = match policyset.is_authorized_batched(request, schema, loader, iterations) {
+        Ok(decision) => Some(decision),
+        Err(BatchedEvalError::InsufficientIterations(_)) => None,
+        Err(e) => return Err(ExecError::BatchedAuthorizationError { error: Box::new(e) }),
+    };
+    let entities = loader.loaded_entities()?;
+    let lean_decision = lean_context
+        .batched_authorization(policyset, schema, request, &entities, iterations)?
+        .decision;
+    if lean_decision != decision {
+        return Err(ExecError::BatchedDecisionMismatch {
+            request: request.to_string(),
+            rust_decision: format!("{decision:?}"),
+            lean_decision: format!("{lean_decision:?}"),
+        });
+    }
+    Ok(decision)
+}
+
+/// Authorize each of the `requests` against `policyset` using batched evaluation with at most
+/// `iterations` iterations, loading entities lazily with `loader`. The decision reached by the Rust
+/// batched evaluator is checked against the lean_ffi's batched authorization on the loaded entities.
+/// Requests that fail (e.g., on a mismatch) are reported individually, and make the command fail at the end.
+pub fn batched_authorize(
+    policyset: &PolicySet,
+    schema: &Schema,
+    requests: &[Request],
+    loader: &mut FileEntityLoader<'_>,
+    iterations: u32,
+    json_output: bool,
+) -> Result<(), ExecError> {
+    let lean_context = CedarLeanFfi::new();
+    let mut results = Vec::new();
+    for request in requests {
+        loader.reset();
+        let outcome = batched_decision(
+            &lean_context,
+            policyset,
+            schema,
+            request,
+            loader,
+            iterations,
+        );
+        results.push(BatchedAuthorizationResult {
+            principal: request_component(request.principal()),
+            action: request_component(request.action()),
+            resource: request_component(request.resource()),
+            decision: outcome
+                .as_ref()
+                .ok()
+                .flatten()
+                .map(|decision| format!("{decision:?}")),
+            round_trips: loader.round_trips(),
+            insufficient_iterations: matches!(outcome, Ok(None)),
+            error: outcome.err().map(|e| e.to_string()),
+        });
+    }
+    let failed = results.iter().filter(|r| r.error.is_some()).count();
+
+    if json_output {
+        serde_json::to_writer_pretty(std::io::stdout(), &results).unwrap();
+    } else {
+        for (ind, result) in results.iter().enumerate() {
+            let outcome = match (&result.decision, &result.error) {
+                (_, Some(error)) => format!("Error ({error})"),
+                (Some(decision), None) => decision.clone(),
+                (None, None) => {
+                    format!("InsufficientIterations (no decision within {iterations} iterations)")
+                }
+            };
+            println!(
+                "Request {} (principal: {}, action: {}, resource: {}): {outcome}, loader round-trips: {}",
+                ind + 1,
+                result.principal,
+                result.action,
+                result.resource,
+                result.round_trips
+            );
+        }
+        println!();
+        println!(
+            "Maximum loader round-trips per request: {}",
+            results.iter().map(|r| r.round_trips).max().unwrap_or(0)
+        );
+        println!(
+            "Requests with insufficient iterations: {} of {}",
+            results.iter().filter(|r| r.insufficient_iterations).count(),
+            results.len()
+        );
+    }
+    if failed > 0 {
+        return Err(ExecError::BatchedAuthorizationFailed {
+            failed,
+            total: results.len(),
+        });
+    }
+    Ok(())
+}
+
+/// The `--json-output` of `evaluate evaluate`
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct EvaluationResult {
+    /// The value the input evaluated to, in the "natural" json format
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<serde_json::Value>,
+    /// The error the evaluation of the input failed with
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<EvaluationError>,
+    /// Whether the input evaluated to the expected output, if one was provided
+    #[serde(skip_serializing_if = "Option::is_none")]
+    matches_expected: Option<bool>,
+}
+
+impl EvaluationResult {
+    fn new(
+        evaluation: Result<RestrictedExpression, EvaluationError>,
+        matches_expected: Option<bool>,
+    ) -> Result<Self, ExecError> {
+        Ok(match evaluation {
+            Ok(value) => Self {
+                value: Some(value_to_json(&value)?),
+                error: None,
+                matches_expected,
+            },
+            Err(error) => Self {
+                value: None,
+                error: Some(error),
+                matches_expected,
+            },
+        })
+    }
+}
+
+fn evaluation_result_error(error: impl std::fmt::Display) -> ExecError {
+    ExecError::EvaluationResultError {
+        error: error.to_string().into(),
+    }
+}
+
+/// Dump the Cedar value `value` to a "natural" json value
+fn value_to_json(value: &RestrictedExpression) -> Result<serde_json::Value, ExecError> {
+    let value: &cedar_policy_core::ast::RestrictedExpr = value.as_ref();
+    value.to_natural_json().map_err(evaluation_result_error)
+}
+
+/// Use the lean_ffi to evaluate the input Cedar `Expression`, and to determine if it evaluates to
+/// the output Cedar `Expression` if one is provided, recording the time spent in Lean in `timings`
+fn evaluate_expr(
+    lean_context: &CedarLeanFfi,
+    input_expr: &Expression,
+    entities: &Entities,
+    request: &Request,
+    expected_output: Option<&Expression>,
+    timings: &mut Timings,
+) -> Result<(Result<RestrictedExpression, EvaluationError>, Option<bool>), ExecError> {
+    let (response, duration) =
+        split_timed(lean_context.evaluate_timed(input_expr, entities, request)?);
+    timings.push(TimedStep::Evaluation, duration);
+    let evaluation = match response {
+        EvaluationResponse::Ok(value) => {
+            Ok(RestrictedExpression::try_from(value).map_err(evaluation_result_error)?)
+        }
+        EvaluationResponse::Error(error) => Err(error),
+    };
+    let matches_expected = match expected_output {
+        Some(output_expr) => {
+            let (matches_expected, duration) = split_timed(lean_context.check_evaluate_timed(
+                input_expr,
+                entities,
+                request,
+                Some(output_expr),
+            )?);
+            timings.push(TimedStep::ExpectedOutput, duration);
+            Some(matches_expected)
+        }
+        None => None,
+    };
+    Ok((evaluation, matches_expected))
+}
+
+/// Like `evaluate`, but return the `--json-output` instead of printing it
+pub(crate) fn evaluation_result(
+    lean_context: &CedarLeanFfi,
+    input_expr: &Expression,
+    entities: &Entities,
+    request: &Request,
+    expected_output: Option<&Expression>,
+) -> Result<EvaluationResult, ExecError> {
+    let (evaluation, matches_expected) = evaluate_expr(
+        lean_context,
+        input_expr,
+        entities,
+        request,
+        expected_output,
+        &mut Timings::default(),
+    )?;
+    EvaluationResult::new(evaluation, matches_expected)
+}
+
+/// Use the lean_ffi to evaluate the input Cedar `Expression` and print the resulting value (or
+/// evaluation error). If an output Cedar `Expression` is provided, instead determine if the input
+/// evaluates to it.
 pub fn evaluate(
     input_expr: &Expression,
     entities: &Entities,
     request: &Request,
     expected_output: Option<&Expression>,
+    json_output: bool,
+    timings: bool,
 ) -> Result<(), ExecError> {
     let lean_context = CedarLeanFfi::new();
-    match expected_output {
-        Some(output_expr) => {
-            let res =
-                lean_context.check_evaluate(input_expr, entities, request, Some(output_expr))?;
-            if res {
-                println!("Input expression evaluated to the expected output expression.")
-            } else {
-                println!("Input expression did not evaluate to the expected output expression.")
-            }
-            Ok(())
+    let mut eval_timings = Timings::default();
+    let (evaluation, matches_expected) = evaluate_expr(
+        &lean_context,
+        input_expr,
+        entities,
+        request,
+        expected_output,
+        &mut eval_timings,
+    )?;
+
+    if json_output {
+        let result = EvaluationResult::new(evaluation, matches_expected)?;
+        let json = if timings {
+            serde_json::to_string_pretty(&TimedOutputSer {
+                results: result,
+                timings: TimingsSer::from(&eval_timings),
+            })
+        } else {
+            serde_json::to_string_pretty(&result)
         }
-        None => {
-            lean_context.print_evaluation(input_expr, entities, request)?;
-            Ok(())
+        .unwrap();
+        println!("{}", json);
+        return Ok(());
+    }
+    match matches_expected {
+        Some(true) => {
+            println!("Input expression evaluated to the expected output expression.")
+        }
+        Some(false) => {
+            println!("Input expression did not evaluate to the expected output expression.")
         }
+        None => (),
     }
+    if matches_expected != Some(true) {
+        match evaluation {
+            Ok(value) => println!("{value}"),
+            Err(error) => println!("evaluate: error during evaluation: {error}"),
+        }
+    }
+    if timings {
+        println!();
+        eval_timings.print_table();
+    }
+    Ok(())
 }
@@ -0,0 +1,153 @@
+/*
+ * Copyright Cedar Contributors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use crate::err::{ContentType, ExecError};
+use cedar_json_test::{json_request, passes_validation, write_test_case};
+use cedar_lean_ffi::Env;
+use cedar_policy::{Authorizer, Policy, PolicySet, SchemaFragment};
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Where (and against which schema) counterexamples are exported as integration tests
+pub struct CexExport {
+    /// Directory in which the test cases are written. Will be created if it doesn't exist.
+    pub dirname: PathBuf,
+    /// The schema file the analysis was run against
+    pub schema_file: PathBuf,
+}
+
+/// The schema counterexamples are exported against, parsed once for all of them
+pub struct ExportSchema {
+    fragment: SchemaFragment,
+    text: String,
+}
+
+impl CexExport {
+    /// Parse the schema file, which is written (in the Cedar schema format) with every test case
+    pub fn parse_schema(&self) -> Result<ExportSchema, ExecError> {
+        let fragment = parse_schema_fragment(&self.schema_file)?;
+        let text = fragment
+            .to_cedarschema()
+            .map_err(|err| ExecError::ParseError {
+                content_type: ContentType::Schema,
+                file_name: self.schema_file.clone(),
+                error: Box::new(err),
+            })?;
+        Ok(ExportSchema { fragment, text })
+    }
+}
+
+/// Writes the counterexample `cex` as an integration test case for `policies`, in the layout
+/// `cedar_drt::dump::dump` also writes (see `cedar_json_test::write_test_case`). The expected
+/// decision is computed by the Rust `Authorizer`.
+pub fn export_cex(
+    export: &CexExport,
+    schema: &ExportSchema,
+    testcasename: &str,
+    description: &str,
+    policies: &PolicySet,
+    cex: &Env,
+) -> Result<(), ExecError> {
+    let policy_text = policies_to_cedar(policies)?;
+    // Policy ids are not preserved by the Cedar text format, so the expected
+    // response is computed on the policies as they will be re-parsed by the test.
+    let reparsed_policies =
+        PolicySet::from_str(&policy_text).map_err(|err| ExecError::ParseError {
+            content_type: ContentType::PolicySet,
+            file_name: export.dirname.join(format!("{testcasename}.cedar")),
+            error: Box::new(err),
+        })?;
+
+    let (request, entities) =
+        cex.clone()
+            .to_request_and_entities()
+            .map_err(|err| ExecError::InternalAnalysisError {
+                error: format!("invalid counterexample: {err}").into(),
+            })?;
+    let response = Authorizer::new().is_authorized(&request, &reparsed_policies, &entities);
+
+    write_test_case(
+        &export.dirname,
+        testcasename,
+        &schema.text,
+        &policy_text,
+        &entities,
+        passes_validation(schema.fragment.clone(), &reparsed_policies),
+        vec![json_request(description.to_string(), &request, &response)],
+    )
+    .map_err(|err| write_error(&export.dirname.join(testcasename), err))
+}
+
+/// The Cedar text of `policies`. The integration test format has no template links, so each
+/// template-linked policy is written as the static policy it stands for (via its JSON form, in
+/// which the slots are filled in).
+fn policies_to_cedar(policies: &PolicySet) -> Result<String, ExecError> {
+    let policy_texts = policies
+        .policies()
+        .map(|policy| {
+            if policy.template_id().is_none() {
+                return Ok(policy.to_string());
+            }
+            let json = policy
+                .to_json()
+                .map_err(|err| ExecError::InternalAnalysisError {
+                    error: Box::new(err),
+                })?;
+            let policy =
+                Policy::from_json(None, json).map_err(|err| ExecError::InternalAnalysisError {
+                    error: Box::new(err),
+                })?;
+            Ok(policy.to_string())
+        })
+        .collect::<Result<Vec<_>, ExecError>>()?;
+    Ok(policy_texts.join("\n"))
+}
+
+/// Parse the schema file as a `SchemaFragment` (picking the format by file extension, as `util::parse_schema` does)
+fn parse_schema_fragment(fname: &PathBuf) -> Result<SchemaFragment, ExecError> {
+    let schema_text = read_to_string(fname).map_err(|err| ExecError::FileReadError {
+        content_type: ContentType::Schema,
+        file_name: fname.to_path_buf(),
+        error: Box::new(err),
+    })?;
+    let fragment = match fname.extension().and_then(|ext| ext.to_str()) {
+        Some("json") => {
+            SchemaFragment::from_json_str(&schema_text).map_err(|err| ExecError::ParseError {
+                content_type: ContentType::SchemaJSON,
+                file_name: fname.to_path_buf(),
+                error: Box::new(err),
+            })?
+        }
+        _ => {
+            SchemaFragment::from_cedarschema_str(&schema_text)
+                .map_err(|err| ExecError::ParseError {
+                    content_type: ContentType::Schema,
+                    file_name: fname.to_path_buf(),
+                    error: Box::new(err),
+                })?
+                .0
+        }
+    };
+    Ok(fragment)
+}
+
+fn write_error(fname: &Path, err: impl std::error::Error + Send + Sync + 'static) -> ExecError {
+    ExecError::FileWriteError {
+        content_type: ContentType::IntegrationTest,
+        file_name: fname.to_path_buf(),
+        error: Box::new(err),
+    }
+}
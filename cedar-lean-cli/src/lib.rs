@@ -17,11 +17,17 @@
 mod analysis;
 mod cli_enums;
 mod cli_exec;
+mod diff;
 mod err;
 mod evaluation;
+mod export;
+mod sarif;
+mod serve;
+mod solver;
 mod symcc;
 mod util;
 mod validation;
+mod verify;
 
 /// Make only CLIArgs and ExecError public
 pub use cli_enums::CliArgs;
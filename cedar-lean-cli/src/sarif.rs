@@ -0,0 +1,400 @@
+/*
+ * Copyright Cedar Contributors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use crate::analysis::{AnalyzePolicyFindings, ExampleEnv, VacuityResult};
+use crate::util::{PolicySpan, RequestEnvSer};
+use cedar_policy::PolicyId;
+use itertools::Itertools;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+const SARIF_VERSION: &str = "2.1.0";
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// The kinds of `analyze policies` findings, each reported as a SARIF rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Rule {
+    VacuousPolicy,
+    RedundantPolicies,
+    PermitShadowedByPermit,
+    PermitOverriddenByForbid,
+    ForbidShadowedByForbid,
+    PolicyAlwaysErrors,
+    PolicyMayError,
+    Undecided,
+}
+
+impl Rule {
+    const ALL: [Rule; 8] = [
+        Rule::VacuousPolicy,
+        Rule::RedundantPolicies,
+        Rule::PermitShadowedByPermit,
+        Rule::PermitOverriddenByForbid,
+        Rule::ForbidShadowedByForbid,
+        Rule::PolicyAlwaysErrors,
+        Rule::PolicyMayError,
+        Rule::Undecided,
+    ];
+
+    fn id(self) -> &'static str {
+        match self {
+            Rule::VacuousPolicy => "vacuous-policy",
+            Rule::RedundantPolicies => "redundant-policies",
+            Rule::PermitShadowedByPermit => "permit-shadowed-by-permit",
+            Rule::PermitOverriddenByForbid => "permit-overridden-by-forbid",
+            Rule::ForbidShadowedByForbid => "forbid-shadowed-by-forbid",
+            Rule::PolicyAlwaysErrors => "policy-always-errors",
+            Rule::PolicyMayError => "policy-may-error",
+            Rule::Undecided => "undecided",
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            Rule::VacuousPolicy => "The policy applies to all or to no authorization requests.",
+            Rule::RedundantPolicies => {
+                "The policy is equivalent to another policy for a request environment."
+            }
+            Rule::PermitShadowedByPermit => {
+                "Every request allowed by the permit policy is allowed by another permit policy."
+            }
+            Rule::PermitOverriddenByForbid => {
+                "Every request allowed by the permit policy is denied by a forbid policy."
+            }
+            Rule::ForbidShadowedByForbid => {
+                "Every request denied by the forbid policy is denied by another forbid policy."
+            }
+            Rule::PolicyAlwaysErrors => {
+                "The policy errors for every authorization request its scope matches, so it never applies."
+            }
+            Rule::PolicyMayError => "The policy errors for some authorization requests.",
+            Rule::Undecided => {
+                "The solver could not decide whether the policy is vacuous, or how it relates to another policy."
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct SarifLog {
+    version: &'static str,
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    runs: Vec<SarifRun>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRun {
+    tool: SarifTool,
+    column_kind: &'static str,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SarifTool {
+    driver: SarifDriver,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifDriver {
+    name: &'static str,
+    version: &'static str,
+    rules: Vec<SarifRule>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRule {
+    id: &'static str,
+    short_description: SarifMessage,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SarifMessage {
+    text: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: &'static str,
+    rule_index: usize,
+    level: &'static str,
+    message: SarifMessage,
+    locations: Vec<SarifLocation>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    related_locations: Vec<SarifLocation>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifLocation {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<usize>,
+    physical_location: SarifPhysicalLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<SarifMessage>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifPhysicalLocation {
+    artifact_location: SarifArtifactLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    region: Option<SarifRegion>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SarifArtifactLocation {
+    uri: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRegion {
+    start_line: usize,
+    start_column: usize,
+    end_line: usize,
+    end_column: usize,
+}
+
+impl From<&PolicySpan> for SarifRegion {
+    fn from(span: &PolicySpan) -> Self {
+        // SARIF end columns are exclusive
+        Self {
+            start_line: span.start_line,
+            start_column: span.start_column,
+            end_line: span.end_line,
+            end_column: span.end_column + 1,
+        }
+    }
+}
+
+/// Builds the SARIF results, locating policies in the file `uri`
+struct SarifBuilder<'a> {
+    uri: &'a str,
+    spans: &'a HashMap<PolicyId, PolicySpan>,
+    results: Vec<SarifResult>,
+}
+
+impl SarifBuilder<'_> {
+    fn location(&self, pid: &PolicyId, id: Option<usize>) -> SarifLocation {
+        SarifLocation {
+            id,
+            physical_location: SarifPhysicalLocation {
+                artifact_location: SarifArtifactLocation {
+                    uri: self.uri.to_string(),
+                },
+                region: self.spans.get(pid).map(SarifRegion::from),
+            },
+            message: id.map(|_| SarifMessage {
+                text: format!("Policy `{pid}`"),
+            }),
+        }
+    }
+
+    /// Report `message` on policy `pid`, with `related` policies as related locations
+    fn add<'b>(
+        &mut self,
+        rule: Rule,
+        pid: &PolicyId,
+        message: String,
+        related: impl IntoIterator<Item = &'b PolicyId>,
+    ) {
+        let related_locations = related
+            .into_iter()
+            .enumerate()
+            .map(|(ind, rpid)| self.location(rpid, Some(ind)))
+            .collect();
+        self.results.push(SarifResult {
+            rule_id: rule.id(),
+            rule_index: Rule::ALL
+                .iter()
+                .position(|r| *r == rule)
+                .expect("every rule is listed in `Rule::ALL`"),
+            level: "warning",
+            message: SarifMessage { text: message },
+            locations: vec![self.location(pid, None)],
+            related_locations,
+        });
+    }
+
+    /// Report every `pid` shadowed/overridden (as described by `verb`) by the policies it maps to
+    fn add_pairs(
+        &mut self,
+        rule: Rule,
+        verb: &str,
+        req_env: &RequestEnvSer,
+        findings: &HashMap<PolicyId, HashSet<PolicyId>>,
+    ) {
+        for (pid, others) in findings.iter().sorted_by_key(|(pid, _)| pid.to_string()) {
+            for opid in others.iter().sorted_by_key(|pid| pid.to_string()) {
+                self.add(
+                    rule,
+                    pid,
+                    format!("Policy `{pid}` {verb} `{opid}` for request environment {req_env}"),
+                    [opid],
+                );
+            }
+        }
+    }
+}
+
+impl SarifLog {
+    /// Convert the findings of `analyze policies` on the PolicySet in file `uri` into a SARIF log.
+    /// `spans` gives the location of each policy within that file.
+    pub(crate) fn new(
+        findings: &AnalyzePolicyFindings,
+        uri: &str,
+        spans: &HashMap<PolicyId, PolicySpan>,
+    ) -> Self {
+        let mut builder = SarifBuilder {
+            uri,
+            spans,
+            results: Vec::new(),
+        };
+
+        for (pid, vr) in findings
+            .vacuous_policies
+            .iter()
+            .sorted_by_key(|(pid, _)| pid.to_string())
+        {
+            let (rule, message) = match vr {
+                VacuityResult::MatchesSome => continue,
+                VacuityResult::MatchesAll => (
+                    Rule::VacuousPolicy,
+                    format!("Policy `{pid}` applies to all authorization requests."),
+                ),
+                VacuityResult::MatchesNone => (
+                    Rule::VacuousPolicy,
+                    format!("Policy `{pid}` applies to no authorization requests."),
+                ),
+                VacuityResult::Unknown => (
+                    Rule::Undecided,
+                    format!("Policy `{pid}` may apply to all or to no authorization requests."),
+                ),
+            };
+            builder.add(rule, pid, message, []);
+        }
+
+        for sig_finding in findings.per_sig_findings.iter() {
+            let req_env = &sig_finding.req_env;
+            for equiv_class in sig_finding.equiv_classes.iter() {
+                for pid in equiv_class.iter().sorted() {
+                    let others = equiv_class.iter().filter(|opid| *opid != pid).sorted();
+                    builder.add(
+                        Rule::RedundantPolicies,
+                        pid,
+                        format!(
+                            "Policy `{pid}` is redundant with {} for request environment {req_env}",
+                            others.clone().map(|opid| format!("`{opid}`")).join(", "),
+                        ),
+                        others,
+                    );
+                }
+            }
+            builder.add_pairs(
+                Rule::PermitShadowedByPermit,
+                "shadowed by",
+                req_env,
+                &sig_finding.permit_shadowed_by_permits,
+            );
+            builder.add_pairs(
+                Rule::PermitOverriddenByForbid,
+                "overridden by",
+                req_env,
+                &sig_finding.permit_overridden_by_forbids,
+            );
+            builder.add_pairs(
+                Rule::ForbidShadowedByForbid,
+                "shadowed by",
+                req_env,
+                &sig_finding.forbid_shadowed_by_forbids,
+            );
+            for (pid, env) in sig_finding
+                .always_errors
+                .iter()
+                .sorted_by_key(|(pid, _)| pid.to_string())
+            {
+                builder.add(
+                    Rule::PolicyAlwaysErrors,
+                    pid,
+                    format!(
+                        "Policy `{pid}` always errors for request environment {req_env} when its scope matches, e.g., for {}",
+                        ExampleEnv(env.clone()).to_string().trim_end()
+                    ),
+                    [],
+                );
+            }
+            for (pid, env) in sig_finding
+                .may_error
+                .iter()
+                .sorted_by_key(|(pid, _)| pid.to_string())
+            {
+                builder.add(
+                    Rule::PolicyMayError,
+                    pid,
+                    format!(
+                        "Policy `{pid}` may error for request environment {req_env}, e.g., for {}",
+                        ExampleEnv(env.clone()).to_string().trim_end()
+                    ),
+                    [],
+                );
+            }
+            for (pid, others) in sig_finding
+                .undecided
+                .iter()
+                .sorted_by_key(|(pid, _)| pid.to_string())
+            {
+                for opid in others.iter().sorted_by_key(|pid| pid.to_string()) {
+                    builder.add(
+                        Rule::Undecided,
+                        pid,
+                        format!(
+                            "Could not decide how policy `{pid}` relates to `{opid}` for request environment {req_env}"
+                        ),
+                        [opid],
+                    );
+                }
+            }
+        }
+
+        Self {
+            version: SARIF_VERSION,
+            schema: SARIF_SCHEMA,
+            runs: vec![SarifRun {
+                tool: SarifTool {
+                    driver: SarifDriver {
+                        name: env!("CARGO_PKG_NAME"),
+                        version: env!("CARGO_PKG_VERSION"),
+                        rules: Rule::ALL
+                            .iter()
+                            .map(|rule| SarifRule {
+                                id: rule.id(),
+                                short_description: SarifMessage {
+                                    text: rule.description().to_string(),
+                                },
+                            })
+                            .collect(),
+                    },
+                },
+                column_kind: "unicodeCodePoints",
+                results: builder.results,
+            }],
+        }
+    }
+}
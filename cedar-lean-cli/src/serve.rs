@@ -0,0 +1,701 @@
+/*
+ * Copyright Cedar Contributors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use crate::analysis::Analyzer;
+use crate::cli_enums::{ContextArg, RequestArgsEnum, ValidationMode};
+use crate::err::ExecError;
+use crate::evaluation;
+use crate::symcc::{self, CheckOptions, CheckResult};
+use crate::util::{self, OpenRequestEnv, SchemaCache};
+use crate::validation;
+use cedar_lean_ffi::{CedarLeanFfi, FfiError, LeanSchema};
+use cedar_policy::{Policy, Request, RequestEnv, Schema};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::io::{BufRead, BufReader, Write};
+use std::num::NonZeroUsize;
+use std::os::unix::fs::FileTypeExt;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+
+/// Serve JSON-RPC 2.0 requests, one per line, read from stdin (or from each connection to a Unix
+/// socket bound at `socket`, if provided). Responses are written one per line.
+pub fn serve(socket: Option<&PathBuf>) -> Result<(), ExecError> {
+    let mut server = Server::new();
+    match socket {
+        None => server.serve_connection(std::io::stdin().lock(), std::io::stdout().lock()),
+        Some(socket) => {
+            let listener = BoundSocket::bind(socket)?;
+            // Connections are served one at a time, as the Lean runtime is initialized for this thread
+            for stream in listener.listener.incoming() {
+                let stream = stream.map_err(serve_error)?;
+                let reader = BufReader::new(stream.try_clone().map_err(serve_error)?);
+                if let Err(err) = server.serve_connection(reader, stream) {
+                    eprintln!("serve: dropped connection: {err}");
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+fn serve_error(error: std::io::Error) -> ExecError {
+    ExecError::ServeError {
+        error: Box::new(error),
+    }
+}
+
+/// A listener on a Unix socket, whose socket file is removed when the listener is dropped
+struct BoundSocket<'a> {
+    path: &'a Path,
+    listener: UnixListener,
+}
+
+impl<'a> BoundSocket<'a> {
+    /// Bind a Unix socket at `path`, first removing a stale socket file left behind by a server
+    /// that did not exit cleanly. A socket that a server is still listening on is not removed.
+    fn bind(path: &'a Path) -> Result<Self, ExecError> {
+        let is_socket =
+            std::fs::symlink_metadata(path).is_ok_and(|metadata| metadata.file_type().is_socket());
+        if is_socket && UnixStream::connect(path).is_err() {
+            std::fs::remove_file(path).map_err(serve_error)?;
+        }
+        let listener = UnixListener::bind(path).map_err(serve_error)?;
+        Ok(Self { path, listener })
+    }
+}
+
+impl Drop for BoundSocket<'_> {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(self.path);
+    }
+}
+
+/// The Lean runtime and schemas shared by every request
+struct Server {
+    /// `CedarLeanFfi`, initialized once and used for every request
+    lean_ffi: CedarLeanFfi,
+    schemas: SchemaCache,
+}
+
+/***************************************************************************************************
+ * JSON-RPC messages
+ ***************************************************************************************************/
+
+#[derive(Debug, Deserialize)]
+struct RpcRequest {
+    jsonrpc: String,
+    /// Absent for notifications, which get no response
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Debug, Serialize)]
+struct RpcResponse {
+    jsonrpc: &'static str,
+    id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<RpcError>,
+}
+
+impl RpcResponse {
+    fn new(id: Value, result: Result<Value, RpcError>) -> Self {
+        let (result, error) = match result {
+            Ok(result) => (Some(result), None),
+            Err(error) => (None, Some(error)),
+        };
+        Self {
+            jsonrpc: "2.0",
+            id,
+            result,
+            error,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    const PARSE_ERROR: i64 = -32700;
+    const INVALID_REQUEST: i64 = -32600;
+    const METHOD_NOT_FOUND: i64 = -32601;
+    const INVALID_PARAMS: i64 = -32602;
+    /// Reserved for implementation-defined server errors; used for any `ExecError`
+    const EXEC_ERROR: i64 = -32000;
+
+    fn new(code: i64, message: impl ToString) -> Self {
+        Self {
+            code,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl From<ExecError> for RpcError {
+    fn from(err: ExecError) -> Self {
+        Self::new(Self::EXEC_ERROR, err)
+    }
+}
+
+impl From<FfiError> for RpcError {
+    fn from(err: FfiError) -> Self {
+        ExecError::from(err).into()
+    }
+}
+
+fn parse_params<T: DeserializeOwned>(params: Value) -> Result<T, RpcError> {
+    serde_json::from_value(params).map_err(|err| RpcError::new(RpcError::INVALID_PARAMS, err))
+}
+
+fn to_json(value: impl Serialize) -> Value {
+    serde_json::to_value(value).unwrap()
+}
+
+/***************************************************************************************************
+ * Method parameters. Parameters are named after the corresponding command line arguments.
+ ***************************************************************************************************/
+
+/// A request, given either by its components or by a file (as for `RequestArgs`)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct RequestParams {
+    principal: Option<String>,
+    action: Option<String>,
+    resource: Option<String>,
+    /// The context as a JSON object
+    context: Option<Value>,
+    context_file: Option<PathBuf>,
+    request_file: Option<PathBuf>,
+}
+
+impl RequestParams {
+    fn parse(self, schema: Option<&Schema>) -> Result<Request, RpcError> {
+        let req_args = match (
+            self.request_file,
+            self.principal,
+            self.action,
+            self.resource,
+        ) {
+            (Some(file_name), None, None, None) => RequestArgsEnum::FromFile { file_name },
+            (None, Some(principal), Some(action), Some(resource)) => {
+                let context = match (self.context, self.context_file) {
+                    (Some(context), _) => ContextArg::FromString {
+                        json_str: context.to_string(),
+                    },
+                    (_, Some(file_name)) => ContextArg::FromFile { file_name },
+                    (None, None) => ContextArg::Default,
+                };
+                RequestArgsEnum::FromArgs {
+                    principal,
+                    action,
+                    resource,
+                    context,
+                }
+            }
+            _ => {
+                return Err(RpcError::new(
+                    RpcError::INVALID_PARAMS,
+                    "either `request-file` or all of `principal`, `action` and `resource` are required",
+                ));
+            }
+        };
+        Ok(req_args.parse(schema)?)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct AuthorizeParams {
+    policyset_file: PathBuf,
+    entities_file: PathBuf,
+    schema_file: Option<PathBuf>,
+    #[serde(flatten)]
+    request: RequestParams,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct EvaluateParams {
+    input_expr_file: PathBuf,
+    entities_file: PathBuf,
+    schema_file: Option<PathBuf>,
+    expected_expr_file: Option<PathBuf>,
+    #[serde(flatten)]
+    request: RequestParams,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct ValidatePolicySetParams {
+    policyset_file: PathBuf,
+    schema_file: PathBuf,
+    /// The validation mode, strict by default. Level-based validation is always strict.
+    validation_mode: Option<ValidationMode>,
+    /// Validate at this level, if provided
+    level: Option<i32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct ValidateRequestParams {
+    schema_file: PathBuf,
+    #[serde(flatten)]
+    request: RequestParams,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct ValidateEntitiesParams {
+    schema_file: PathBuf,
+    entities_file: PathBuf,
+}
+
+/// The request environments to restrict an analysis to (as for `RequestEnvArgs`)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct RequestEnvParams {
+    principal_type: Option<String>,
+    action_name: Option<String>,
+    resource_type: Option<String>,
+}
+
+impl RequestEnvParams {
+    fn parse(self) -> Result<OpenRequestEnv, ExecError> {
+        OpenRequestEnv::new(self.principal_type, self.action_name, self.resource_type)
+    }
+}
+
+/// The parameters of every `symcc` method, where `T` holds the policies to analyze
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct SymccParams<T> {
+    schema_file: PathBuf,
+    #[serde(flatten)]
+    args: T,
+    #[serde(flatten)]
+    req_env: RequestEnvParams,
+    #[serde(default)]
+    cex: bool,
+    max_cex: Option<NonZeroUsize>,
+    #[serde(default)]
+    minimize_cex: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct PolicyParams {
+    policy_file: PathBuf,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct PolicySetParams {
+    policyset_file: PathBuf,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct ComparePolicySetParams {
+    pset1_file: PathBuf,
+    pset2_file: PathBuf,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct PropertyParams {
+    policyset_file: PathBuf,
+    property_file: PathBuf,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct ComparePolicyParams {
+    policy1_file: PathBuf,
+    policy2_file: PathBuf,
+    policy_id1: Option<String>,
+    policy_id2: Option<String>,
+}
+
+impl ComparePolicyParams {
+    fn parse(self) -> Result<(Policy, Policy), ExecError> {
+        Ok((
+            util::parse_policy_with_id(&self.policy1_file, self.policy_id1.as_deref())?,
+            util::parse_policy_with_id(&self.policy2_file, self.policy_id2.as_deref())?,
+        ))
+    }
+}
+
+/// The parameters of every `analyze` method, where `T` holds the PolicySet(s) to analyze
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct AnalyzeParams<T> {
+    schema_file: PathBuf,
+    #[serde(flatten)]
+    args: T,
+    #[serde(flatten)]
+    req_env: RequestEnvParams,
+    /// For `analyze/policies`, whether to also report policies that may error (off by default)
+    #[serde(default)]
+    check_errors: bool,
+    /// For `analyze/compare`, whether to also report which policies cause the differences
+    #[serde(default)]
+    attribute: bool,
+}
+
+/***************************************************************************************************
+ * Methods
+ ***************************************************************************************************/
+
+impl Server {
+    fn new() -> Self {
+        Self {
+            lean_ffi: CedarLeanFfi::new(),
+            schemas: SchemaCache::default(),
+        }
+    }
+
+    /// Answer every request read from `reader`, until it is closed
+    fn serve_connection(
+        &mut self,
+        reader: impl BufRead,
+        mut writer: impl Write,
+    ) -> Result<(), ExecError> {
+        for line in reader.lines() {
+            let line = line.map_err(serve_error)?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(response) = self.handle(&line) {
+                writeln!(writer, "{}", serde_json::to_string(&response).unwrap())
+                    .and_then(|()| writer.flush())
+                    .map_err(serve_error)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Answer the request in `line`, unless it is a notification
+    fn handle(&mut self, line: &str) -> Option<RpcResponse> {
+        let request = match serde_json::from_str::<Value>(line) {
+            Ok(request) => request,
+            Err(err) => {
+                let error = RpcError::new(RpcError::PARSE_ERROR, err);
+                return Some(RpcResponse::new(Value::Null, Err(error)));
+            }
+        };
+        let request = match serde_json::from_value::<RpcRequest>(request) {
+            Ok(request) if request.jsonrpc == "2.0" => request,
+            Ok(_) => {
+                let error = RpcError::new(RpcError::INVALID_REQUEST, "`jsonrpc` must be \"2.0\"");
+                return Some(RpcResponse::new(Value::Null, Err(error)));
+            }
+            Err(err) => {
+                let error = RpcError::new(RpcError::INVALID_REQUEST, err);
+                return Some(RpcResponse::new(Value::Null, Err(error)));
+            }
+        };
+        let result = self.dispatch(&request.method, request.params);
+        request.id.map(|id| RpcResponse::new(id, result))
+    }
+
+    fn dispatch(&mut self, method: &str, params: Value) -> Result<Value, RpcError> {
+        match method {
+            "evaluate/authorize" => self.authorize(parse_params(params)?),
+            "evaluate/evaluate" => self.evaluate(parse_params(params)?),
+            "validate/policy-set" => self.validate_policyset(parse_params(params)?),
+            "validate/request" => self.validate_request(parse_params(params)?),
+            "validate/entities" => self.validate_entities(parse_params(params)?),
+            "symcc/check-never-errors" => self.symcc_check(
+                params,
+                false,
+                |lean_ffi, args: PolicyParams, schema, req_envs, opts| {
+                    let policy = util::parse_policy(&args.policy_file)?;
+                    symcc::check_never_errors(lean_ffi, &policy, schema, req_envs, opts)
+                },
+            ),
+            "symcc/check-always-matches" => self.symcc_check(
+                params,
+                false,
+                |lean_ffi, args: PolicyParams, schema, req_envs, opts| {
+                    let policy = util::parse_policy(&args.policy_file)?;
+                    symcc::check_always_matches(lean_ffi, &policy, schema, req_envs, opts)
+                },
+            ),
+            "symcc/check-never-matches" => self.symcc_check(
+                params,
+                false,
+                |lean_ffi, args: PolicyParams, schema, req_envs, opts| {
+                    let policy = util::parse_policy(&args.policy_file)?;
+                    symcc::check_never_matches(lean_ffi, &policy, schema, req_envs, opts)
+                },
+            ),
+            "symcc/check-always-allows" => self.symcc_check(
+                params,
+                false,
+                |lean_ffi, args: PolicySetParams, schema, req_envs, opts| {
+                    let policyset = util::parse_policyset(&args.policyset_file)?;
+                    symcc::check_always_allows(lean_ffi, &policyset, schema, req_envs, opts)
+                },
+            ),
+            "symcc/check-always-denies" => self.symcc_check(
+                params,
+                false,
+                |lean_ffi, args: PolicySetParams, schema, req_envs, opts| {
+                    let policyset = util::parse_policyset(&args.policyset_file)?;
+                    symcc::check_always_denies(lean_ffi, &policyset, schema, req_envs, opts)
+                },
+            ),
+            "symcc/check-equivalent" => self.symcc_check(
+                params,
+                false,
+                |lean_ffi, args: ComparePolicySetParams, schema, req_envs, opts| {
+                    let pset1 = util::parse_policyset(&args.pset1_file)?;
+                    let pset2 = util::parse_policyset(&args.pset2_file)?;
+                    symcc::check_equivalent(lean_ffi, &pset1, &pset2, schema, req_envs, opts)
+                },
+            ),
+            "symcc/check-implies" => self.symcc_check(
+                params,
+                false,
+                |lean_ffi, args: ComparePolicySetParams, schema, req_envs, opts| {
+                    let pset1 = util::parse_policyset(&args.pset1_file)?;
+                    let pset2 = util::parse_policyset(&args.pset2_file)?;
+                    symcc::check_implies(lean_ffi, &pset1, &pset2, schema, req_envs, opts)
+                },
+            ),
+            "symcc/check-disjoint" => self.symcc_check(
+                params,
+                false,
+                |lean_ffi, args: ComparePolicySetParams, schema, req_envs, opts| {
+                    let pset1 = util::parse_policyset(&args.pset1_file)?;
+                    let pset2 = util::parse_policyset(&args.pset2_file)?;
+                    symcc::check_disjoint(lean_ffi, &pset1, &pset2, schema, req_envs, opts)
+                },
+            ),
+            "symcc/check-property" => self.symcc_check(
+                params,
+                false,
+                |lean_ffi, args: PropertyParams, schema, req_envs, opts| {
+                    let policyset = util::parse_policyset(&args.policyset_file)?;
+                    let property = util::parse_expression(&args.property_file)?;
+                    symcc::check_property(lean_ffi, &policyset, &property, schema, req_envs, opts)
+                },
+            ),
+            // counterexamples are always reported when comparing two policies
+            "symcc/check-matches-equivalent" => self.symcc_check(
+                params,
+                true,
+                |lean_ffi, args: ComparePolicyParams, schema, req_envs, opts| {
+                    let (policy1, policy2) = args.parse()?;
+                    symcc::check_matches_equivalent(
+                        lean_ffi, &policy1, &policy2, schema, req_envs, opts,
+                    )
+                },
+            ),
+            "symcc/check-matches-implies" => self.symcc_check(
+                params,
+                true,
+                |lean_ffi, args: ComparePolicyParams, schema, req_envs, opts| {
+                    let (policy1, policy2) = args.parse()?;
+                    symcc::check_matches_implies(
+                        lean_ffi, &policy1, &policy2, schema, req_envs, opts,
+                    )
+                },
+            ),
+            "symcc/check-matches-disjoint" => self.symcc_check(
+                params,
+                true,
+                |lean_ffi, args: ComparePolicyParams, schema, req_envs, opts| {
+                    let (policy1, policy2) = args.parse()?;
+                    symcc::check_matches_disjoint(
+                        lean_ffi, &policy1, &policy2, schema, req_envs, opts,
+                    )
+                },
+            ),
+            "analyze/policies" => {
+                self.analyze(params, |analyzer, args: PolicySetParams, req_env| {
+                    let policyset = util::parse_policyset(&args.policyset_file)?;
+                    analyzer.analyze_policyset_json(&policyset, req_env)
+                })
+            }
+            "analyze/compare" => {
+                self.analyze(params, |analyzer, args: ComparePolicySetParams, req_env| {
+                    let pset1 = util::parse_policyset(&args.pset1_file)?;
+                    let pset2 = util::parse_policyset(&args.pset2_file)?;
+                    analyzer.compare_policysets_json(&pset1, &pset2, req_env)
+                })
+            }
+            "analyze/minimize" => {
+                self.analyze(params, |analyzer, args: PolicySetParams, req_env| {
+                    let policyset = util::parse_policyset(&args.policyset_file)?;
+                    analyzer.minimize_policyset_json(&policyset, req_env)
+                })
+            }
+            _ => Err(RpcError::new(
+                RpcError::METHOD_NOT_FOUND,
+                format!("unknown method `{method}`"),
+            )),
+        }
+    }
+
+    fn authorize(&mut self, params: AuthorizeParams) -> Result<Value, RpcError> {
+        let policyset = util::parse_policyset(&params.policyset_file)?;
+        let schema = self
+            .schemas
+            .get_optional(&self.lean_ffi, params.schema_file.as_ref())?;
+        let request = params.request.parse(schema)?;
+        let entities = util::parse_entities(&params.entities_file, schema)?;
+        Ok(to_json(evaluation::authorization_result(
+            &self.lean_ffi,
+            &policyset,
+            &entities,
+            &request,
+        )?))
+    }
+
+    fn evaluate(&mut self, params: EvaluateParams) -> Result<Value, RpcError> {
+        let input_expr = util::parse_expression(&params.input_expr_file)?;
+        let schema = self
+            .schemas
+            .get_optional(&self.lean_ffi, params.schema_file.as_ref())?;
+        let entities = util::parse_entities(&params.entities_file, schema)?;
+        let request = params.request.parse(schema)?;
+        let expected_expr = params
+            .expected_expr_file
+            .map(|fname| util::parse_expression(&fname))
+            .transpose()?;
+        Ok(to_json(evaluation::evaluation_result(
+            &self.lean_ffi,
+            &input_expr,
+            &entities,
+            &request,
+            expected_expr.as_ref(),
+        )?))
+    }
+
+    fn validate_policyset(&mut self, params: ValidatePolicySetParams) -> Result<Value, RpcError> {
+        let policyset = util::parse_policyset(&params.policyset_file)?;
+        let schema = &self
+            .schemas
+            .get(&self.lean_ffi, &params.schema_file)?
+            .schema;
+        let lean_ffi = &self.lean_ffi;
+        let result = match params.level {
+            None => {
+                let mode = params
+                    .validation_mode
+                    .unwrap_or(ValidationMode::Strict)
+                    .to_cedar();
+                validation::policyset_validation_result(
+                    &policyset,
+                    lean_ffi.validate(&policyset, schema, &mode)?,
+                    |pset| lean_ffi.validate_timed(pset, schema, &mode),
+                    None,
+                )?
+            }
+            Some(level) => validation::policyset_validation_result(
+                &policyset,
+                lean_ffi.level_validate(&policyset, schema, level)?,
+                |pset| lean_ffi.level_validate_timed(pset, schema, level),
+                None,
+            )?,
+        };
+        Ok(to_json(result))
+    }
+
+    fn validate_request(&mut self, params: ValidateRequestParams) -> Result<Value, RpcError> {
+        let schema = &self
+            .schemas
+            .get(&self.lean_ffi, &params.schema_file)?
+            .schema;
+        let request = params.request.parse(Some(schema))?;
+        let response = self.lean_ffi.validate_request(schema, &request)?;
+        Ok(to_json(validation::type_error_result(response)))
+    }
+
+    fn validate_entities(&mut self, params: ValidateEntitiesParams) -> Result<Value, RpcError> {
+        let schema = &self
+            .schemas
+            .get(&self.lean_ffi, &params.schema_file)?
+            .schema;
+        let entities = util::parse_entities(&params.entities_file, Some(schema))?;
+        let response = self.lean_ffi.validate_entities(schema, &entities)?;
+        Ok(to_json(validation::type_error_result(response)))
+    }
+
+    /// Run the `symcc` check `check` as described by `params`, reporting counterexamples if
+    /// requested (or always, if `always_cex` is set)
+    fn symcc_check<T: DeserializeOwned>(
+        &mut self,
+        params: Value,
+        always_cex: bool,
+        check: impl FnOnce(
+            &CedarLeanFfi,
+            T,
+            LeanSchema,
+            &[RequestEnv],
+            &CheckOptions,
+        ) -> Result<Vec<CheckResult>, ExecError>,
+    ) -> Result<Value, RpcError> {
+        let params: SymccParams<T> = parse_params(params)?;
+        let schema = self.schemas.get(&self.lean_ffi, &params.schema_file)?;
+        let req_envs = params.req_env.parse()?.to_request_envs(&schema.schema)?;
+        let opts = CheckOptions {
+            cex: always_cex || params.cex,
+            max_cex: params.max_cex.unwrap_or(NonZeroUsize::MIN),
+            minimize: params.minimize_cex.then(|| schema.schema.clone()),
+            json_output: true,
+            export: None,
+            timings: false,
+        };
+        let results = check(
+            &self.lean_ffi,
+            params.args,
+            schema.lean_schema.clone(),
+            &req_envs,
+            &opts,
+        )?;
+        Ok(to_json(symcc::results_ser(&results, &req_envs)))
+    }
+
+    /// Run the analysis `analyze` as described by `params`
+    fn analyze<T: DeserializeOwned>(
+        &mut self,
+        params: Value,
+        analyze: impl FnOnce(&Analyzer<'_>, T, &OpenRequestEnv) -> Result<Value, ExecError>,
+    ) -> Result<Value, RpcError> {
+        let params: AnalyzeParams<T> = parse_params(params)?;
+        let schema = self.schemas.get(&self.lean_ffi, &params.schema_file)?;
+        let req_env = params.req_env.parse()?;
+        let mut analyzer = Analyzer::with_lean_schema(
+            &self.lean_ffi,
+            &schema.schema,
+            schema.lean_schema.clone(),
+            true,
+        );
+        analyzer.set_check_errors(params.check_errors);
+        analyzer.set_attribute_differences(params.attribute);
+        Ok(analyze(&analyzer, params.args, &req_env)?)
+    }
+}
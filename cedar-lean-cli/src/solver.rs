@@ -0,0 +1,129 @@
+/*
+ * Copyright Cedar Contributors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use crate::cli_enums::SolverArgs;
+use crate::err::{ContentType, ExecError};
+use cedar_lean_ffi::FfiError;
+use std::env;
+use std::ffi::OsStr;
+use std::fmt::Write as _;
+use std::fs::{self, DirBuilder, OpenOptions};
+use std::io::{self, Write as _};
+use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The environment variable Lean reads the path of the CVC5 executable from
+const CVC5_VAR: &str = "CVC5";
+
+/// The error reported by Lean when the solver answers `unknown` to a query
+const UNKNOWN_ERROR: &str = "Solver returned unknown";
+
+/// Whether `err` is due to the solver not deciding a query, e.g., because it ran out of time
+pub(crate) fn is_unknown(err: &FfiError) -> bool {
+    matches!(err, FfiError::LeanBackendError(msg) if msg.contains(UNKNOWN_ERROR))
+}
+
+/// A private directory holding a script that runs the solver within the configured limits,
+/// deleted when dropped
+pub(crate) struct SolverWrapper {
+    dir: PathBuf,
+}
+
+impl Drop for SolverWrapper {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// Point Lean at the solver selected by `args`.
+///
+/// Lean always runs the solver as `$CVC5 --quiet --lang smt`, so limits are enforced by a wrapper
+/// script that `CVC5` is set to.
+///
+/// # Safety
+///
+/// Sets `CVC5` with `env::set_var`, so no other thread may be running (or reading the
+/// environment) when this is called.
+pub(crate) unsafe fn configure_solver(
+    args: &SolverArgs,
+) -> Result<Option<SolverWrapper>, ExecError> {
+    if args.timeout.is_none() && args.memory_limit.is_none() {
+        if let Some(solver) = &args.solver {
+            // SAFETY: guaranteed by the caller
+            unsafe { env::set_var(CVC5_VAR, solver) }
+        }
+        return Ok(None);
+    }
+    // Like the fuzz harness, fall back to the `cvc5` on the PATH
+    let solver = match &args.solver {
+        Some(solver) => solver.clone().into_os_string(),
+        None => env::var_os(CVC5_VAR).unwrap_or_else(|| "cvc5".into()),
+    };
+    let mut script = String::from("#!/bin/sh\n");
+    if let Some(megabytes) = args.memory_limit {
+        writeln!(script, "ulimit -v {}", megabytes.get() * 1024).unwrap();
+    }
+    write!(script, "exec {}", shell_quote(&solver)).unwrap();
+    if let Some(seconds) = args.timeout {
+        // The solver answers `unknown` to a `check-sat` that exceeds the limit
+        write!(script, " --tlimit-per={}", seconds.get() * 1000).unwrap();
+    }
+    script.push_str(" \"$@\"\n");
+
+    let write_error = |file_name: &PathBuf, err: io::Error| ExecError::FileWriteError {
+        content_type: ContentType::SolverWrapper,
+        file_name: file_name.clone(),
+        error: Box::new(err),
+    };
+    let dir = private_temp_dir().map_err(|err| write_error(&env::temp_dir(), err))?;
+    let wrapper = SolverWrapper { dir };
+    let path = wrapper.dir.join("solver");
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o700)
+        .open(&path)
+        .and_then(|mut file| file.write_all(script.as_bytes()))
+        .map_err(|err| write_error(&path, err))?;
+    // SAFETY: guaranteed by the caller
+    unsafe { env::set_var(CVC5_VAR, &path) }
+    Ok(Some(wrapper))
+}
+
+/// Create a fresh directory in the temporary directory that only the current user can access.
+/// Creating the directory fails if its path exists, so it cannot be a file (or symlink) planted
+/// there by another user.
+fn private_temp_dir() -> io::Result<PathBuf> {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.subsec_nanos());
+    let mut attempt = 0;
+    loop {
+        let dir = env::temp_dir().join(format!(
+            "cedar-lean-cli-solver-{}-{nanos}-{attempt}",
+            std::process::id()
+        ));
+        match DirBuilder::new().mode(0o700).create(&dir) {
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists && attempt < 100 => attempt += 1,
+            result => return result.map(|()| dir),
+        }
+    }
+}
+
+/// `s` as a single-quoted `sh` word
+fn shell_quote(s: &OsStr) -> String {
+    format!("'{}'", s.to_string_lossy().replace('\'', r"'\''"))
+}
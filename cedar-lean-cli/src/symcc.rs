@@ -13,81 +13,426 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use crate::analysis::ExampleEnv;
 use crate::err::ExecError;
-use crate::util::{OpenRequestEnv, ReqEnv};
-use cedar_lean_ffi::CedarLeanFfi;
-use cedar_policy::{Policy, PolicySet, RequestEnv, Schema};
+use crate::export::{CexExport, export_cex};
+use crate::solver::is_unknown;
+use crate::util::{
+    CheckResultSer, OpenRequestEnv, ReqEnv, RequestEnvSer, TimedOutputSer, TimedStep, Timings,
+    TimingsSer, singleton_policyset, split_timed,
+};
+use cedar_lean_ffi::{CedarLeanFfi, Env, FfiError, LeanSchema};
+use cedar_policy::{
+    AuthorizationError, Authorizer, Decision, Expression, Policy, PolicyId, PolicySet, RequestEnv,
+    Schema,
+};
+use itertools::Itertools;
 use std::iter::zip;
+use std::num::NonZeroUsize;
+use std::time::Duration;
 
 /// Run lean backend for analysis `check-never-errors`
 pub fn run_check_never_errors(
     policy: Policy,
     schema: Schema,
     request_env: &OpenRequestEnv,
+    opts: &CheckOptions,
 ) -> Result<(), ExecError> {
     let lean_context = CedarLeanFfi::new();
     let req_envs = request_env.to_request_envs(&schema)?;
     let schema = lean_context.load_lean_schema_object(&schema)?;
-    let mut results = Vec::new();
-    for req_env in req_envs.iter() {
-        results.push(lean_context.run_check_never_errors(&policy, schema.clone(), req_env)?);
+    let results = check_never_errors(&lean_context, &policy, schema, &req_envs, opts)?;
+    print_results(
+        &results,
+        &req_envs,
+        request_env,
+        opts,
+        print_check_never_errors_results,
+    );
+    if let Some(export) = &opts.export {
+        let policyset = policy_to_policyset(&policy)?;
+        export_cexs(
+            export,
+            "check-never-errors",
+            &[("", &policyset)],
+            &results,
+            &req_envs,
+        )?;
     }
-    print_check_never_errors_results(&results, &req_envs, request_env);
     Ok(())
 }
 
+/// Compute the results of analysis `check-never-errors` for each of the `req_envs`
+pub(crate) fn check_never_errors(
+    lean_context: &CedarLeanFfi,
+    policy: &Policy,
+    schema: LeanSchema,
+    req_envs: &[RequestEnv],
+    opts: &CheckOptions,
+) -> Result<Vec<CheckResult>, ExecError> {
+    let mut results = run_per_req_env(
+        req_envs,
+        opts,
+        |req_env| {
+            lean_context
+                .run_check_never_errors_timed(policy, schema.clone(), req_env)
+                .map(split_timed)
+        },
+        |req_env, found| {
+            let policy = guard_found(policy, found)?;
+            Ok(split_timed(
+                lean_context.run_check_never_errors_with_cex_timed(
+                    &policy,
+                    schema.clone(),
+                    req_env,
+                )?,
+            ))
+        },
+    )?;
+    if let Some(cex_schema) = &opts.minimize {
+        minimize_cexs(&mut results, cex_schema, &[&policy_to_policyset(policy)?]);
+    }
+    Ok(results)
+}
+
+/// Run lean backend for analysis `check-always-matches`
+pub fn run_check_always_matches(
+    policy: Policy,
+    schema: Schema,
+    request_env: &OpenRequestEnv,
+    opts: &CheckOptions,
+) -> Result<(), ExecError> {
+    let lean_context = CedarLeanFfi::new();
+    let req_envs = request_env.to_request_envs(&schema)?;
+    let schema = lean_context.load_lean_schema_object(&schema)?;
+    let results = check_always_matches(&lean_context, &policy, schema, &req_envs, opts)?;
+    print_results(
+        &results,
+        &req_envs,
+        request_env,
+        opts,
+        print_check_always_matches_results,
+    );
+    if let Some(export) = &opts.export {
+        let policyset = policy_to_policyset(&policy)?;
+        export_cexs(
+            export,
+            "check-always-matches",
+            &[("", &policyset)],
+            &results,
+            &req_envs,
+        )?;
+    }
+    Ok(())
+}
+
+/// Compute the results of analysis `check-always-matches` for each of the `req_envs`
+pub(crate) fn check_always_matches(
+    lean_context: &CedarLeanFfi,
+    policy: &Policy,
+    schema: LeanSchema,
+    req_envs: &[RequestEnv],
+    opts: &CheckOptions,
+) -> Result<Vec<CheckResult>, ExecError> {
+    let mut results = run_per_req_env(
+        req_envs,
+        opts,
+        |req_env| {
+            lean_context
+                .run_check_always_matches_timed(policy, schema.clone(), req_env)
+                .map(split_timed)
+        },
+        |req_env, found| {
+            if found.is_empty() {
+                return Ok(split_timed(
+                    lean_context.run_check_always_matches_with_cex_timed(
+                        policy,
+                        schema.clone(),
+                        req_env,
+                    )?,
+                ));
+            }
+            // `policy` matches all requests iff `matches_policyset(policy)` allows all requests
+            Ok(split_timed(lean_context.run_check_implies_with_cex_timed(
+                &block_found(&allow_all()?, found)?,
+                &matches_policyset(policy)?,
+                schema.clone(),
+                req_env,
+            )?))
+        },
+    )?;
+    if let Some(cex_schema) = &opts.minimize {
+        minimize_cexs(&mut results, cex_schema, &[&matches_policyset(policy)?]);
+    }
+    Ok(results)
+}
+
+/// Run lean backend for analysis `check-never-matches`
+pub fn run_check_never_matches(
+    policy: Policy,
+    schema: Schema,
+    request_env: &OpenRequestEnv,
+    opts: &CheckOptions,
+) -> Result<(), ExecError> {
+    let lean_context = CedarLeanFfi::new();
+    let req_envs = request_env.to_request_envs(&schema)?;
+    let schema = lean_context.load_lean_schema_object(&schema)?;
+    let results = check_never_matches(&lean_context, &policy, schema, &req_envs, opts)?;
+    print_results(
+        &results,
+        &req_envs,
+        request_env,
+        opts,
+        print_check_never_matches_results,
+    );
+    if let Some(export) = &opts.export {
+        let policyset = policy_to_policyset(&policy)?;
+        export_cexs(
+            export,
+            "check-never-matches",
+            &[("", &policyset)],
+            &results,
+            &req_envs,
+        )?;
+    }
+    Ok(())
+}
+
+/// Compute the results of analysis `check-never-matches` for each of the `req_envs`
+pub(crate) fn check_never_matches(
+    lean_context: &CedarLeanFfi,
+    policy: &Policy,
+    schema: LeanSchema,
+    req_envs: &[RequestEnv],
+    opts: &CheckOptions,
+) -> Result<Vec<CheckResult>, ExecError> {
+    let mut results = run_per_req_env(
+        req_envs,
+        opts,
+        |req_env| {
+            lean_context
+                .run_check_never_matches_timed(policy, schema.clone(), req_env)
+                .map(split_timed)
+        },
+        |req_env, found| {
+            if found.is_empty() {
+                return Ok(split_timed(
+                    lean_context.run_check_never_matches_with_cex_timed(
+                        policy,
+                        schema.clone(),
+                        req_env,
+                    )?,
+                ));
+            }
+            // `policy` matches no requests iff `matches_policyset(policy)` denies all requests
+            Ok(split_timed(
+                lean_context.run_check_always_denies_with_cex_timed(
+                    &block_found(&matches_policyset(policy)?, found)?,
+                    schema.clone(),
+                    req_env,
+                )?,
+            ))
+        },
+    )?;
+    if let Some(cex_schema) = &opts.minimize {
+        minimize_cexs(&mut results, cex_schema, &[&matches_policyset(policy)?]);
+    }
+    Ok(results)
+}
+
 /// Run lean backend for analysis `check-always-allows`
 pub fn run_check_always_allows(
     policyset: PolicySet,
     schema: Schema,
     request_env: &OpenRequestEnv,
+    opts: &CheckOptions,
 ) -> Result<(), ExecError> {
     let lean_context = CedarLeanFfi::new();
     let req_envs = request_env.to_request_envs(&schema)?;
     let schema = lean_context.load_lean_schema_object(&schema)?;
-    let mut results = Vec::new();
-    for req_env in req_envs.iter() {
-        results.push(lean_context.run_check_always_allows(&policyset, schema.clone(), req_env)?);
+    let results = check_always_allows(&lean_context, &policyset, schema, &req_envs, opts)?;
+    print_results(
+        &results,
+        &req_envs,
+        request_env,
+        opts,
+        print_check_always_allows_results,
+    );
+    if let Some(export) = &opts.export {
+        export_cexs(
+            export,
+            "check-always-allows",
+            &[("", &policyset)],
+            &results,
+            &req_envs,
+        )?;
     }
-    print_check_always_allows_results(&results, &req_envs, request_env);
     Ok(())
 }
 
+/// Compute the results of analysis `check-always-allows` for each of the `req_envs`
+pub(crate) fn check_always_allows(
+    lean_context: &CedarLeanFfi,
+    policyset: &PolicySet,
+    schema: LeanSchema,
+    req_envs: &[RequestEnv],
+    opts: &CheckOptions,
+) -> Result<Vec<CheckResult>, ExecError> {
+    let mut results = run_per_req_env(
+        req_envs,
+        opts,
+        |req_env| {
+            lean_context
+                .run_check_always_allows_timed(policyset, schema.clone(), req_env)
+                .map(split_timed)
+        },
+        |req_env, found| {
+            if found.is_empty() {
+                return Ok(split_timed(
+                    lean_context.run_check_always_allows_with_cex_timed(
+                        policyset,
+                        schema.clone(),
+                        req_env,
+                    )?,
+                ));
+            }
+            // `policyset` allows all requests iff it allows every request the allow-all PolicySet does
+            Ok(split_timed(lean_context.run_check_implies_with_cex_timed(
+                &block_found(&allow_all()?, found)?,
+                policyset,
+                schema.clone(),
+                req_env,
+            )?))
+        },
+    )?;
+    if let Some(cex_schema) = &opts.minimize {
+        minimize_cexs(&mut results, cex_schema, &[policyset]);
+    }
+    Ok(results)
+}
+
 /// Run lean backend for analysis `check-always-denies`
 pub fn run_check_always_denies(
     policyset: PolicySet,
     schema: Schema,
     request_env: &OpenRequestEnv,
+    opts: &CheckOptions,
 ) -> Result<(), ExecError> {
     let lean_context = CedarLeanFfi::new();
     let req_envs = request_env.to_request_envs(&schema)?;
     let schema = lean_context.load_lean_schema_object(&schema)?;
-    let mut results = Vec::new();
-    for req_env in req_envs.iter() {
-        results.push(lean_context.run_check_always_denies(&policyset, schema.clone(), req_env)?);
+    let results = check_always_denies(&lean_context, &policyset, schema, &req_envs, opts)?;
+    print_results(
+        &results,
+        &req_envs,
+        request_env,
+        opts,
+        print_check_always_denies_results,
+    );
+    if let Some(export) = &opts.export {
+        export_cexs(
+            export,
+            "check-always-denies",
+            &[("", &policyset)],
+            &results,
+            &req_envs,
+        )?;
     }
-    print_check_always_denies_results(&results, &req_envs, request_env);
     Ok(())
 }
 
+/// Compute the results of analysis `check-always-denies` for each of the `req_envs`
+pub(crate) fn check_always_denies(
+    lean_context: &CedarLeanFfi,
+    policyset: &PolicySet,
+    schema: LeanSchema,
+    req_envs: &[RequestEnv],
+    opts: &CheckOptions,
+) -> Result<Vec<CheckResult>, ExecError> {
+    let mut results = run_per_req_env(
+        req_envs,
+        opts,
+        |req_env| {
+            lean_context
+                .run_check_always_denies_timed(policyset, schema.clone(), req_env)
+                .map(split_timed)
+        },
+        |req_env, found| {
+            Ok(split_timed(
+                lean_context.run_check_always_denies_with_cex_timed(
+                    &block_found(policyset, found)?,
+                    schema.clone(),
+                    req_env,
+                )?,
+            ))
+        },
+    )?;
+    if let Some(cex_schema) = &opts.minimize {
+        minimize_cexs(&mut results, cex_schema, &[policyset]);
+    }
+    Ok(results)
+}
+
 /// Run lean backend for analysis `check-equivalent`
 pub fn run_check_equivalent(
     pset1: PolicySet,
     pset2: PolicySet,
     schema: Schema,
     request_env: &OpenRequestEnv,
+    opts: &CheckOptions,
 ) -> Result<(), ExecError> {
     let lean_context = CedarLeanFfi::new();
     let req_envs = request_env.to_request_envs(&schema)?;
     let schema = lean_context.load_lean_schema_object(&schema)?;
-    let mut results = Vec::new();
-    for req_env in req_envs.iter() {
-        results.push(lean_context.run_check_equivalent(&pset1, &pset2, schema.clone(), req_env)?);
+    let results = check_equivalent(&lean_context, &pset1, &pset2, schema, &req_envs, opts)?;
+    print_results(
+        &results,
+        &req_envs,
+        request_env,
+        opts,
+        print_check_equivalent_results,
+    );
+    if let Some(export) = &opts.export {
+        let psets = [("pset1", &pset1), ("pset2", &pset2)];
+        export_cexs(export, "check-equivalent", &psets, &results, &req_envs)?;
     }
-    print_check_equivalent_results(&results, &req_envs, request_env);
     Ok(())
 }
 
+/// Compute the results of analysis `check-equivalent` for each of the `req_envs`
+pub(crate) fn check_equivalent(
+    lean_context: &CedarLeanFfi,
+    pset1: &PolicySet,
+    pset2: &PolicySet,
+    schema: LeanSchema,
+    req_envs: &[RequestEnv],
+    opts: &CheckOptions,
+) -> Result<Vec<CheckResult>, ExecError> {
+    let mut results = run_per_req_env(
+        req_envs,
+        opts,
+        |req_env| {
+            lean_context
+                .run_check_equivalent_timed(pset1, pset2, schema.clone(), req_env)
+                .map(split_timed)
+        },
+        |req_env, found| {
+            Ok(split_timed(
+                lean_context.run_check_equivalent_with_cex_timed(
+                    &block_found(pset1, found)?,
+                    &block_found(pset2, found)?,
+                    schema.clone(),
+                    req_env,
+                )?,
+            ))
+        },
+    )?;
+    if let Some(cex_schema) = &opts.minimize {
+        minimize_cexs(&mut results, cex_schema, &[pset1, pset2]);
+    }
+    Ok(results)
+}
+
 /// Run lean backend for analysis `check-implies`
 /// Checks if pset1 => pset2 --- i.e., If every request allowed by pset1 is also allowed by pset2
 pub fn run_check_implies(
@@ -95,35 +440,435 @@ pub fn run_check_implies(
     pset2: PolicySet,
     schema: Schema,
     request_env: &OpenRequestEnv,
+    opts: &CheckOptions,
 ) -> Result<(), ExecError> {
     let lean_context = CedarLeanFfi::new();
     let req_envs = request_env.to_request_envs(&schema)?;
     let schema = lean_context.load_lean_schema_object(&schema)?;
-    let mut results = Vec::new();
-    for req_env in req_envs.iter() {
-        results.push(lean_context.run_check_implies(&pset1, &pset2, schema.clone(), req_env)?);
+    let results = check_implies(&lean_context, &pset1, &pset2, schema, &req_envs, opts)?;
+    print_results(
+        &results,
+        &req_envs,
+        request_env,
+        opts,
+        print_check_implies_results,
+    );
+    if let Some(export) = &opts.export {
+        let psets = [("pset1", &pset1), ("pset2", &pset2)];
+        export_cexs(export, "check-implies", &psets, &results, &req_envs)?;
     }
-    print_check_implies_results(&results, &req_envs, request_env);
     Ok(())
 }
-/// Run lean backend for analysis `check-denies`
+
+/// Compute the results of analysis `check-implies` for each of the `req_envs`
+pub(crate) fn check_implies(
+    lean_context: &CedarLeanFfi,
+    pset1: &PolicySet,
+    pset2: &PolicySet,
+    schema: LeanSchema,
+    req_envs: &[RequestEnv],
+    opts: &CheckOptions,
+) -> Result<Vec<CheckResult>, ExecError> {
+    let mut results = run_per_req_env(
+        req_envs,
+        opts,
+        |req_env| {
+            lean_context
+                .run_check_implies_timed(pset1, pset2, schema.clone(), req_env)
+                .map(split_timed)
+        },
+        |req_env, found| {
+            Ok(split_timed(lean_context.run_check_implies_with_cex_timed(
+                &block_found(pset1, found)?,
+                pset2,
+                schema.clone(),
+                req_env,
+            )?))
+        },
+    )?;
+    if let Some(cex_schema) = &opts.minimize {
+        minimize_cexs(&mut results, cex_schema, &[pset1, pset2]);
+    }
+    Ok(results)
+}
+
+/// Run lean backend for analysis `check-disjoint`
 pub fn run_check_disjoint(
     pset1: PolicySet,
     pset2: PolicySet,
     schema: Schema,
     request_env: &OpenRequestEnv,
+    opts: &CheckOptions,
+) -> Result<(), ExecError> {
+    let lean_context = CedarLeanFfi::new();
+    let req_envs = request_env.to_request_envs(&schema)?;
+    let schema = lean_context.load_lean_schema_object(&schema)?;
+    let results = check_disjoint(&lean_context, &pset1, &pset2, schema, &req_envs, opts)?;
+    print_results(
+        &results,
+        &req_envs,
+        request_env,
+        opts,
+        print_check_disjoint_results,
+    );
+    if let Some(export) = &opts.export {
+        let psets = [("pset1", &pset1), ("pset2", &pset2)];
+        export_cexs(export, "check-disjoint", &psets, &results, &req_envs)?;
+    }
+    Ok(())
+}
+
+/// Compute the results of analysis `check-disjoint` for each of the `req_envs`
+pub(crate) fn check_disjoint(
+    lean_context: &CedarLeanFfi,
+    pset1: &PolicySet,
+    pset2: &PolicySet,
+    schema: LeanSchema,
+    req_envs: &[RequestEnv],
+    opts: &CheckOptions,
+) -> Result<Vec<CheckResult>, ExecError> {
+    let mut results = run_per_req_env(
+        req_envs,
+        opts,
+        |req_env| {
+            lean_context
+                .run_check_disjoint_timed(pset1, pset2, schema.clone(), req_env)
+                .map(split_timed)
+        },
+        |req_env, found| {
+            Ok(split_timed(
+                lean_context.run_check_disjoint_with_cex_timed(
+                    &block_found(pset1, found)?,
+                    pset2,
+                    schema.clone(),
+                    req_env,
+                )?,
+            ))
+        },
+    )?;
+    if let Some(cex_schema) = &opts.minimize {
+        minimize_cexs(&mut results, cex_schema, &[pset1, pset2]);
+    }
+    Ok(results)
+}
+
+/// Run lean backend for analysis `check-matches-equivalent`
+pub fn run_check_matches_equivalent(
+    policy1: Policy,
+    policy2: Policy,
+    schema: Schema,
+    request_env: &OpenRequestEnv,
+    opts: &CheckOptions,
+) -> Result<(), ExecError> {
+    let lean_context = CedarLeanFfi::new();
+    let req_envs = request_env.to_request_envs(&schema)?;
+    let schema = lean_context.load_lean_schema_object(&schema)?;
+    let results =
+        check_matches_equivalent(&lean_context, &policy1, &policy2, schema, &req_envs, opts)?;
+    print_results(
+        &results,
+        &req_envs,
+        request_env,
+        opts,
+        print_check_matches_equivalent_results,
+    );
+    if let Some(export) = &opts.export {
+        let policyset1 = policy_to_policyset(&policy1)?;
+        let policyset2 = policy_to_policyset(&policy2)?;
+        let psets = [("policy1", &policyset1), ("policy2", &policyset2)];
+        export_cexs(
+            export,
+            "check-matches-equivalent",
+            &psets,
+            &results,
+            &req_envs,
+        )?;
+    }
+    Ok(())
+}
+
+/// Compute the results of analysis `check-matches-equivalent` for each of the `req_envs`
+pub(crate) fn check_matches_equivalent(
+    lean_context: &CedarLeanFfi,
+    policy1: &Policy,
+    policy2: &Policy,
+    schema: LeanSchema,
+    req_envs: &[RequestEnv],
+    opts: &CheckOptions,
+) -> Result<Vec<CheckResult>, ExecError> {
+    let mut results = run_per_req_env(
+        req_envs,
+        opts,
+        |req_env| {
+            lean_context
+                .run_check_matches_equivalent_timed(policy1, policy2, schema.clone(), req_env)
+                .map(split_timed)
+        },
+        |req_env, found| {
+            if found.is_empty() {
+                return Ok(split_timed(
+                    lean_context.run_check_matches_equivalent_with_cex_timed(
+                        policy1,
+                        policy2,
+                        schema.clone(),
+                        req_env,
+                    )?,
+                ));
+            }
+            Ok(split_timed(
+                lean_context.run_check_equivalent_with_cex_timed(
+                    &block_found(&matches_policyset(policy1)?, found)?,
+                    &block_found(&matches_policyset(policy2)?, found)?,
+                    schema.clone(),
+                    req_env,
+                )?,
+            ))
+        },
+    )?;
+    if let Some(cex_schema) = &opts.minimize {
+        minimize_cexs(
+            &mut results,
+            cex_schema,
+            &[&matches_policyset(policy1)?, &matches_policyset(policy2)?],
+        );
+    }
+    Ok(results)
+}
+
+/// Run lean backend for analysis `check-matches-implies`
+/// Checks if policy1 => policy2 --- i.e., If every request matched by policy1 is also matched by policy2
+pub fn run_check_matches_implies(
+    policy1: Policy,
+    policy2: Policy,
+    schema: Schema,
+    request_env: &OpenRequestEnv,
+    opts: &CheckOptions,
+) -> Result<(), ExecError> {
+    let lean_context = CedarLeanFfi::new();
+    let req_envs = request_env.to_request_envs(&schema)?;
+    let schema = lean_context.load_lean_schema_object(&schema)?;
+    let results =
+        check_matches_implies(&lean_context, &policy1, &policy2, schema, &req_envs, opts)?;
+    print_results(
+        &results,
+        &req_envs,
+        request_env,
+        opts,
+        print_check_matches_implies_results,
+    );
+    if let Some(export) = &opts.export {
+        let policyset1 = policy_to_policyset(&policy1)?;
+        let policyset2 = policy_to_policyset(&policy2)?;
+        let psets = [("policy1", &policyset1), ("policy2", &policyset2)];
+        export_cexs(export, "check-matches-implies", &psets, &results, &req_envs)?;
+    }
+    Ok(())
+}
+
+/// Compute the results of analysis `check-matches-implies` for each of the `req_envs`
+pub(crate) fn check_matches_implies(
+    lean_context: &CedarLeanFfi,
+    policy1: &Policy,
+    policy2: &Policy,
+    schema: LeanSchema,
+    req_envs: &[RequestEnv],
+    opts: &CheckOptions,
+) -> Result<Vec<CheckResult>, ExecError> {
+    let mut results = run_per_req_env(
+        req_envs,
+        opts,
+        |req_env| {
+            lean_context
+                .run_check_matches_implies_timed(policy1, policy2, schema.clone(), req_env)
+                .map(split_timed)
+        },
+        |req_env, found| {
+            if found.is_empty() {
+                return Ok(split_timed(
+                    lean_context.run_check_matches_implies_with_cex_timed(
+                        policy1,
+                        policy2,
+                        schema.clone(),
+                        req_env,
+                    )?,
+                ));
+            }
+            Ok(split_timed(lean_context.run_check_implies_with_cex_timed(
+                &block_found(&matches_policyset(policy1)?, found)?,
+                &matches_policyset(policy2)?,
+                schema.clone(),
+                req_env,
+            )?))
+        },
+    )?;
+    if let Some(cex_schema) = &opts.minimize {
+        minimize_cexs(
+            &mut results,
+            cex_schema,
+            &[&matches_policyset(policy1)?, &matches_policyset(policy2)?],
+        );
+    }
+    Ok(results)
+}
+
+/// Run lean backend for analysis `check-matches-disjoint`
+pub fn run_check_matches_disjoint(
+    policy1: Policy,
+    policy2: Policy,
+    schema: Schema,
+    request_env: &OpenRequestEnv,
+    opts: &CheckOptions,
+) -> Result<(), ExecError> {
+    let lean_context = CedarLeanFfi::new();
+    let req_envs = request_env.to_request_envs(&schema)?;
+    let schema = lean_context.load_lean_schema_object(&schema)?;
+    let results =
+        check_matches_disjoint(&lean_context, &policy1, &policy2, schema, &req_envs, opts)?;
+    print_results(
+        &results,
+        &req_envs,
+        request_env,
+        opts,
+        print_check_matches_disjoint_results,
+    );
+    if let Some(export) = &opts.export {
+        let policyset1 = policy_to_policyset(&policy1)?;
+        let policyset2 = policy_to_policyset(&policy2)?;
+        let psets = [("policy1", &policyset1), ("policy2", &policyset2)];
+        export_cexs(
+            export,
+            "check-matches-disjoint",
+            &psets,
+            &results,
+            &req_envs,
+        )?;
+    }
+    Ok(())
+}
+
+/// Compute the results of analysis `check-matches-disjoint` for each of the `req_envs`
+pub(crate) fn check_matches_disjoint(
+    lean_context: &CedarLeanFfi,
+    policy1: &Policy,
+    policy2: &Policy,
+    schema: LeanSchema,
+    req_envs: &[RequestEnv],
+    opts: &CheckOptions,
+) -> Result<Vec<CheckResult>, ExecError> {
+    let mut results = run_per_req_env(
+        req_envs,
+        opts,
+        |req_env| {
+            lean_context
+                .run_check_matches_disjoint_timed(policy1, policy2, schema.clone(), req_env)
+                .map(split_timed)
+        },
+        |req_env, found| {
+            if found.is_empty() {
+                return Ok(split_timed(
+                    lean_context.run_check_matches_disjoint_with_cex_timed(
+                        policy1,
+                        policy2,
+                        schema.clone(),
+                        req_env,
+                    )?,
+                ));
+            }
+            Ok(split_timed(
+                lean_context.run_check_disjoint_with_cex_timed(
+                    &block_found(&matches_policyset(policy1)?, found)?,
+                    &matches_policyset(policy2)?,
+                    schema.clone(),
+                    req_env,
+                )?,
+            ))
+        },
+    )?;
+    if let Some(cex_schema) = &opts.minimize {
+        minimize_cexs(
+            &mut results,
+            cex_schema,
+            &[&matches_policyset(policy1)?, &matches_policyset(policy2)?],
+        );
+    }
+    Ok(results)
+}
+
+/// Run lean backend for analysis `check-property`.
+///
+/// The property is checked as `check-implies` of the PolicySet against the PolicySet allowing
+/// exactly the requests satisfying the property (see `property_to_policyset`). A request on which
+/// the property errors is therefore not allowed by the latter, and does not satisfy the property.
+pub fn run_check_property(
+    policyset: PolicySet,
+    property: Expression,
+    schema: Schema,
+    request_env: &OpenRequestEnv,
+    opts: &CheckOptions,
 ) -> Result<(), ExecError> {
     let lean_context = CedarLeanFfi::new();
     let req_envs = request_env.to_request_envs(&schema)?;
     let schema = lean_context.load_lean_schema_object(&schema)?;
-    let mut results = Vec::new();
-    for req_env in req_envs.iter() {
-        results.push(lean_context.run_check_disjoint(&pset1, &pset2, schema.clone(), req_env)?);
+    let results = check_property(
+        &lean_context,
+        &policyset,
+        &property,
+        schema,
+        &req_envs,
+        opts,
+    )?;
+    print_results(
+        &results,
+        &req_envs,
+        request_env,
+        opts,
+        print_check_property_results,
+    );
+    if let Some(export) = &opts.export {
+        export_cexs(
+            export,
+            "check-property",
+            &[("", &policyset)],
+            &results,
+            &req_envs,
+        )?;
     }
-    print_check_disjoint_results(&results, &req_envs, request_env);
     Ok(())
 }
 
+/// Compute the results of analysis `check-property` for each of the `req_envs`
+pub(crate) fn check_property(
+    lean_context: &CedarLeanFfi,
+    policyset: &PolicySet,
+    property: &Expression,
+    schema: LeanSchema,
+    req_envs: &[RequestEnv],
+    opts: &CheckOptions,
+) -> Result<Vec<CheckResult>, ExecError> {
+    let property = property_to_policyset(property)?;
+    let mut results = run_per_req_env(
+        req_envs,
+        opts,
+        |req_env| {
+            lean_context
+                .run_check_implies_timed(policyset, &property, schema.clone(), req_env)
+                .map(split_timed)
+        },
+        |req_env, found| {
+            Ok(split_timed(lean_context.run_check_implies_with_cex_timed(
+                &block_found(policyset, found)?,
+                &property,
+                schema.clone(),
+                req_env,
+            )?))
+        },
+    )?;
+    if let Some(cex_schema) = &opts.minimize {
+        minimize_cexs(&mut results, cex_schema, &[policyset, &property]);
+    }
+    Ok(results)
+}
+
 /// Prints to stdout the SMTLib script produced by the lean backend for analysis `check-never-errors`
 pub fn print_check_never_errors(
     policy: Policy,
@@ -146,6 +891,50 @@ pub fn print_check_never_errors(
     Ok(())
 }
 
+/// Prints to stdout the SMTLib script produced by the lean backend for analysis `check-always-matches`
+pub fn print_check_always_matches(
+    policy: Policy,
+    schema: Schema,
+    request_env: &OpenRequestEnv,
+) -> Result<(), ExecError> {
+    let lean_context = CedarLeanFfi::new();
+    let req_envs = request_env.to_request_envs(&schema)?;
+    let schema = lean_context.load_lean_schema_object(&schema)?;
+    for req_env in req_envs {
+        println!(";;");
+        println!(
+            ";; SMTLib encoding for RequestEnv {}",
+            ReqEnv::Env(req_env.clone())
+        );
+        println!(";;");
+        lean_context.print_check_always_matches(&policy, schema.clone(), &req_env)?;
+        println!();
+    }
+    Ok(())
+}
+
+/// Prints to stdout the SMTLib script produced by the lean backend for analysis `check-never-matches`
+pub fn print_check_never_matches(
+    policy: Policy,
+    schema: Schema,
+    request_env: &OpenRequestEnv,
+) -> Result<(), ExecError> {
+    let lean_context = CedarLeanFfi::new();
+    let req_envs = request_env.to_request_envs(&schema)?;
+    let schema = lean_context.load_lean_schema_object(&schema)?;
+    for req_env in req_envs {
+        println!(";;");
+        println!(
+            ";; SMTLib encoding for RequestEnv {}",
+            ReqEnv::Env(req_env.clone())
+        );
+        println!(";;");
+        lean_context.print_check_never_matches(&policy, schema.clone(), &req_env)?;
+        println!();
+    }
+    Ok(())
+}
+
 /// Prints to stdout the SMTLib script produced by the lean backend for analysis `check-always-allows`
 pub fn print_check_always_allows(
     policyset: PolicySet,
@@ -259,6 +1048,490 @@ pub fn print_check_disjoint(
     Ok(())
 }
 
+/// Prints to stdout the SMTLib script produced by the lean backend for analysis `check-matches-equivalent`
+pub fn print_check_matches_equivalent(
+    policy1: Policy,
+    policy2: Policy,
+    schema: Schema,
+    request_env: &OpenRequestEnv,
+) -> Result<(), ExecError> {
+    let lean_context = CedarLeanFfi::new();
+    let req_envs = request_env.to_request_envs(&schema)?;
+    let schema = lean_context.load_lean_schema_object(&schema)?;
+    for req_env in req_envs {
+        println!(";;");
+        println!(
+            ";; SMTLib encoding for RequestEnv {}",
+            ReqEnv::Env(req_env.clone())
+        );
+        println!(";;");
+        lean_context.print_check_matches_equivalent(
+            &policy1,
+            &policy2,
+            schema.clone(),
+            &req_env,
+        )?;
+        println!();
+    }
+    Ok(())
+}
+
+/// Prints to stdout the SMTLib script produced by the lean backend for analysis `check-matches-implies`
+pub fn print_check_matches_implies(
+    policy1: Policy,
+    policy2: Policy,
+    schema: Schema,
+    request_env: &OpenRequestEnv,
+) -> Result<(), ExecError> {
+    let lean_context = CedarLeanFfi::new();
+    let req_envs = request_env.to_request_envs(&schema)?;
+    let schema = lean_context.load_lean_schema_object(&schema)?;
+    for req_env in req_envs {
+        println!(";;");
+        println!(
+            ";; SMTLib encoding for RequestEnv {}",
+            ReqEnv::Env(req_env.clone())
+        );
+        println!(";;");
+        lean_context.print_check_matches_implies(&policy1, &policy2, schema.clone(), &req_env)?;
+        println!();
+    }
+    Ok(())
+}
+
+/// Prints to stdout the SMTLib script produced by the lean backend for analysis `check-matches-disjoint`
+pub fn print_check_matches_disjoint(
+    policy1: Policy,
+    policy2: Policy,
+    schema: Schema,
+    request_env: &OpenRequestEnv,
+) -> Result<(), ExecError> {
+    let lean_context = CedarLeanFfi::new();
+    let req_envs = request_env.to_request_envs(&schema)?;
+    let schema = lean_context.load_lean_schema_object(&schema)?;
+    for req_env in req_envs {
+        println!(";;");
+        println!(
+            ";; SMTLib encoding for RequestEnv {}",
+            ReqEnv::Env(req_env.clone())
+        );
+        println!(";;");
+        lean_context.print_check_matches_disjoint(&policy1, &policy2, schema.clone(), &req_env)?;
+        println!();
+    }
+    Ok(())
+}
+
+/// Prints to stdout the SMTLib script produced by the lean backend for analysis `check-property`,
+/// which is the script of `check-implies` against the PolicySet allowing exactly the requests
+/// satisfying the property
+pub fn print_check_property(
+    policyset: PolicySet,
+    property: Expression,
+    schema: Schema,
+    request_env: &OpenRequestEnv,
+) -> Result<(), ExecError> {
+    let property = property_to_policyset(&property)?;
+    print_check_implies(policyset, property, schema, request_env)
+}
+
+/// Options controlling how the results of a `symcc` check are computed and reported
+pub struct CheckOptions {
+    /// Whether to compute and report a counterexample for each request environment where the check does not hold
+    pub cex: bool,
+    /// The maximum number of distinct counterexamples to report per request environment
+    pub max_cex: NonZeroUsize,
+    /// If set, shrink each counterexample to a minimal one that has the same authorization outcome
+    /// and conforms to this schema
+    pub minimize: Option<Schema>,
+    /// Whether to report the results in .json format
+    pub json_output: bool,
+    /// Where to export counterexamples as integration test cases, if at all
+    pub export: Option<CexExport>,
+    /// Whether to also report the time spent in Lean for each request environment
+    pub timings: bool,
+}
+
+/// The result of a `symcc` check for a single `RequestEnv`
+pub(crate) struct CheckResult {
+    /// Whether the check holds, or `None` if the solver could not decide it (e.g., it timed out)
+    pub(crate) holds: Option<bool>,
+    /// Counterexamples with pairwise distinct (principal, resource) pairs
+    pub(crate) cexs: Vec<Env>,
+    /// The time spent in Lean computing this result
+    pub(crate) duration: Duration,
+}
+
+/// Runs the check for every `RequestEnv`, calling `check_with_cex` instead of `check` when counterexamples are requested.
+///
+/// `check_with_cex` is given the counterexamples found so far for the `RequestEnv`, and must only
+/// return counterexamples whose (principal, resource) pair differs from each of them.
+fn run_per_req_env(
+    req_envs: &[RequestEnv],
+    opts: &CheckOptions,
+    check: impl Fn(&RequestEnv) -> Result<(bool, Duration), FfiError>,
+    check_with_cex: impl Fn(&RequestEnv, &[Env]) -> Result<(Option<Env>, Duration), ExecError>,
+) -> Result<Vec<CheckResult>, ExecError> {
+    req_envs
+        .iter()
+        .map(|req_env| {
+            if opts.cex {
+                let mut cexs: Vec<Env> = Vec::new();
+                let mut duration = Duration::ZERO;
+                let mut unknown = false;
+                while cexs.len() < opts.max_cex.get() {
+                    let (cex, cex_duration) = match check_with_cex(req_env, &cexs) {
+                        Ok(result) => result,
+                        Err(ExecError::LeanFFIError(err)) if is_unknown(&err) => {
+                            unknown = true;
+                            break;
+                        }
+                        Err(err) => return Err(err),
+                    };
+                    duration += cex_duration;
+                    match cex {
+                        Some(cex) if !cexs.iter().any(|found| cex_key(found) == cex_key(&cex)) => {
+                            cexs.push(cex)
+                        }
+                        // Either no further counterexample exists, or the solver did not respect
+                        // the blocking constraints; stop rather than report duplicates
+                        _ => break,
+                    }
+                }
+                // A counterexample found before the solver gave up still refutes the check
+                let holds = if !cexs.is_empty() {
+                    Some(false)
+                } else if unknown {
+                    None
+                } else {
+                    Some(true)
+                };
+                Ok(CheckResult {
+                    holds,
+                    cexs,
+                    duration,
+                })
+            } else {
+                let (holds, duration) = match check(req_env) {
+                    Ok((holds, duration)) => (Some(holds), duration),
+                    Err(err) if is_unknown(&err) => (None, Duration::ZERO),
+                    Err(err) => return Err(err.into()),
+                };
+                Ok(CheckResult {
+                    holds,
+                    cexs: Vec::new(),
+                    duration,
+                })
+            }
+        })
+        .collect()
+}
+
+/// The serializable form of the `results` for each of the `req_envs`
+pub(crate) fn results_ser(results: &[CheckResult], req_envs: &[RequestEnv]) -> Vec<CheckResultSer> {
+    zip(req_envs.iter(), results.iter())
+        .map(|(req_env, result)| CheckResultSer::new(req_env, result.holds, &result.cexs))
+        .collect()
+}
+
+/// Prints the results either as .json or using `print_table` followed by any counterexamples
+fn print_results(
+    results: &[CheckResult],
+    req_envs: &[RequestEnv],
+    open_req_env: &OpenRequestEnv,
+    opts: &CheckOptions,
+    print_table: fn(&[bool], &[RequestEnv], &OpenRequestEnv),
+) {
+    if opts.json_output {
+        if opts.timings {
+            let timed = TimedOutputSer {
+                results: results_ser(results, req_envs),
+                timings: TimingsSer::from(&check_timings(results, req_envs)),
+            };
+            serde_json::to_writer_pretty(std::io::stdout(), &timed).unwrap();
+        } else {
+            serde_json::to_writer_pretty(std::io::stdout(), &results_ser(results, req_envs))
+                .unwrap();
+        }
+        return;
+    }
+    // The summary and table only cover the request environments where the solver decided the check
+    let mut decided_req_envs = Vec::new();
+    let mut holds = Vec::new();
+    let mut unknown_req_envs = Vec::new();
+    for (req_env, result) in zip(req_envs.iter(), results.iter()) {
+        match result.holds {
+            Some(result_holds) => {
+                decided_req_envs.push(req_env.clone());
+                holds.push(result_holds);
+            }
+            None => unknown_req_envs.push(req_env.clone()),
+        }
+    }
+    if !unknown_req_envs.is_empty() {
+        println!(
+            "The solver could not decide the check for {} of {} request signatures",
+            unknown_req_envs.len(),
+            req_envs.len()
+        );
+        println!();
+    }
+    if unknown_req_envs.is_empty() || !decided_req_envs.is_empty() {
+        print_table(&holds, &decided_req_envs, open_req_env);
+    }
+    if !unknown_req_envs.is_empty() {
+        if !decided_req_envs.is_empty() {
+            println!();
+        }
+        println!("Unknown request signatures:");
+        let sig_widths = SigWidths::from_req_envs(&unknown_req_envs);
+        let res_width = 7; // Unknown
+        sig_widths.print_header(res_width, "Result");
+        for req_env in unknown_req_envs.iter() {
+            sig_widths.print_row(req_env, res_width, "Unknown");
+        }
+        sig_widths.print_footer(res_width);
+    }
+    for (req_env, result) in zip(req_envs.iter(), results.iter()) {
+        for (ind, cex) in result.cexs.iter().enumerate() {
+            println!();
+            if result.cexs.len() == 1 {
+                println!(
+                    "Counterexample for RequestEnv {}:",
+                    ReqEnv::Env(req_env.clone())
+                );
+            } else {
+                println!(
+                    "Counterexample {} of {} for RequestEnv {}:",
+                    ind + 1,
+                    result.cexs.len(),
+                    ReqEnv::Env(req_env.clone())
+                );
+            }
+            print!("{}", ExampleEnv(cex.clone()));
+        }
+    }
+    if opts.timings {
+        println!();
+        check_timings(results, req_envs).print_table();
+    }
+}
+
+/// The time spent in Lean computing the `results` for each of the `req_envs`
+fn check_timings(results: &[CheckResult], req_envs: &[RequestEnv]) -> Timings {
+    let mut timings = Timings::default();
+    for (req_env, result) in zip(req_envs.iter(), results.iter()) {
+        timings.push(
+            TimedStep::ReqEnv(RequestEnvSer::new(req_env)),
+            result.duration,
+        );
+    }
+    timings
+}
+
+/// Exports every counterexample in `results` as an integration test case for each of the `policysets`
+fn export_cexs(
+    export: &CexExport,
+    check_name: &str,
+    policysets: &[(&str, &PolicySet)],
+    results: &[CheckResult],
+    req_envs: &[RequestEnv],
+) -> Result<(), ExecError> {
+    let schema = export.parse_schema()?;
+    for (ind, (req_env, result)) in zip(req_envs.iter(), results.iter()).enumerate() {
+        for (cex_ind, cex) in result.cexs.iter().enumerate() {
+            let description = format!(
+                "Counterexample to {check_name} for RequestEnv {}",
+                ReqEnv::Env(req_env.clone())
+            );
+            // The first counterexample keeps the name it has when only one is requested
+            let basename = if cex_ind == 0 {
+                format!("{check_name}_{ind}")
+            } else {
+                format!("{check_name}_{ind}_cex{cex_ind}")
+            };
+            for (label, policyset) in policysets {
+                let testcasename = if label.is_empty() {
+                    basename.clone()
+                } else {
+                    format!("{basename}_{label}")
+                };
+                export_cex(export, &schema, &testcasename, &description, policyset, cex)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The PolicySet allowing exactly the requests satisfying `property`, so that a PolicySet satisfies
+/// `property` iff it implies this PolicySet. As the policy is not satisfied when `property` errors,
+/// neither is the property.
+fn property_to_policyset(property: &Expression) -> Result<PolicySet, ExecError> {
+    let policy = Policy::parse(
+        Some(PolicyId::new("property")),
+        format!("permit(principal, action, resource) when {{ {property} }};"),
+    )
+    .map_err(|err| ExecError::InternalAnalysisError {
+        error: Box::new(err),
+    })?;
+    policy_to_policyset(&policy)
+}
+
+/// The PolicySet containing only `policy`. A template-linked policy (e.g., selected with
+/// `--policy-id` from a JSON PolicySet) comes without its PolicySet, so it is first turned into the
+/// static policy with its slots filled in.
+fn policy_to_policyset(policy: &Policy) -> Result<PolicySet, ExecError> {
+    if policy.is_static() {
+        singleton_policyset(&PolicySet::new(), policy)
+    } else {
+        let json = policy.to_json().map_err(internal_error)?;
+        let policy = Policy::from_json(Some(policy.id().clone()), json).map_err(internal_error)?;
+        singleton_policyset(&PolicySet::new(), &policy)
+    }
+}
+
+/***************************************************************************************************
+ * Functions to enumerate distinct counterexamples
+ *
+ * Further counterexamples are forced to differ from the ones found so far in their (principal,
+ * resource) pair, by adding a forbid policy for each pair found so far (`block_found`). Checks that
+ * are not about what a PolicySet allows are first restated as such (e.g., `policy` matches all
+ * requests iff `matches_policyset(policy)` allows all requests, and a PolicySet allows all requests
+ * iff the allow-all PolicySet implies it).
+ ***************************************************************************************************/
+
+/// The (principal, resource) pair of a counterexample, which tells it apart from the others found for
+/// the same request environment (and thus the same action). Counterexamples that only differ in
+/// their context or entities are not told apart.
+fn cex_key(cex: &Env) -> (String, String) {
+    (
+        cex.request.principal.to_string(),
+        cex.request.resource.to_string(),
+    )
+}
+
+fn internal_error(err: impl std::error::Error + Send + Sync + 'static) -> ExecError {
+    ExecError::InternalAnalysisError {
+        error: Box::new(err),
+    }
+}
+
+fn parse_internal_policy(id: &str, text: &str) -> Result<Policy, ExecError> {
+    Policy::parse(Some(PolicyId::new(id)), text).map_err(internal_error)
+}
+
+/// `policyset` with a forbid policy for the (principal, resource) pair of each of the `found` counterexamples
+fn block_found(policyset: &PolicySet, found: &[Env]) -> Result<PolicySet, ExecError> {
+    let mut blocked = policyset.clone();
+    for (ind, cex) in found.iter().enumerate() {
+        let (principal, resource) = cex_key(cex);
+        let policy = parse_internal_policy(
+            &format!("__blocked_cex{ind}"),
+            &format!("forbid(principal == {principal}, action, resource == {resource});"),
+        )?;
+        blocked
+            .add(policy)
+            .map_err(|err| ExecError::PolicyIntoPolicySetError {
+                error: Box::new(err),
+            })?;
+    }
+    Ok(blocked)
+}
+
+/// `policy` with a leading condition for each of the `found` counterexamples, requiring a different
+/// (principal, resource) pair. Conditions are evaluated in order, so the result errors exactly when
+/// `policy` errors on a request with a different pair.
+fn guard_found(policy: &Policy, found: &[Env]) -> Result<Policy, ExecError> {
+    if found.is_empty() {
+        return Ok(policy.clone());
+    }
+    let mut conditions = Vec::new();
+    for cex in found {
+        let (principal, resource) = cex_key(cex);
+        let guard = parse_internal_policy(
+            "__guard",
+            &format!(
+                "permit(principal, action, resource) when {{ !(principal == {principal} && resource == {resource}) }};"
+            ),
+        )?;
+        conditions.push(guard.to_json().map_err(internal_error)?["conditions"][0].clone());
+    }
+    let mut json = policy.to_json().map_err(internal_error)?;
+    if let Some(serde_json::Value::Array(existing)) = json.get("conditions") {
+        conditions.extend(existing.iter().cloned());
+    }
+    json["conditions"] = serde_json::Value::Array(conditions);
+    Policy::from_json(Some(policy.id().clone()), json).map_err(internal_error)
+}
+
+/// The PolicySet allowing all requests
+fn allow_all() -> Result<PolicySet, ExecError> {
+    policy_to_policyset(&parse_internal_policy(
+        "__allow_all",
+        "permit(principal, action, resource);",
+    )?)
+}
+
+/// The PolicySet allowing exactly the requests matched by `policy`
+fn matches_policyset(policy: &Policy) -> Result<PolicySet, ExecError> {
+    let mut json = policy.to_json().map_err(internal_error)?;
+    json["effect"] = serde_json::Value::from("permit");
+    policy_to_policyset(
+        &Policy::from_json(Some(policy.id().clone()), json).map_err(internal_error)?,
+    )
+}
+
+/***************************************************************************************************
+ * Functions to minimize counterexamples
+ *
+ * A counterexample is shrunk (see `Env::minimize`) as long as it conforms to the schema and the Rust
+ * authorizer gives the same outcome on it for each of the PolicySets the check is about, which
+ * preserves the difference that makes it a counterexample.
+ ***************************************************************************************************/
+
+/// The decision of each of `policysets` on `cex`, and the (sorted) ids of its policies that errored
+fn authorization_outcome(
+    cex: &Env,
+    policysets: &[&PolicySet],
+) -> Option<Vec<(Decision, Vec<PolicyId>)>> {
+    let (request, entities) = cex.clone().to_request_and_entities().ok()?;
+    let authorizer = Authorizer::new();
+    Some(
+        policysets
+            .iter()
+            .map(|policyset| {
+                let response = authorizer.is_authorized(&request, policyset, &entities);
+                (
+                    response.decision(),
+                    response
+                        .diagnostics()
+                        .errors()
+                        .map(|err| match err {
+                            AuthorizationError::PolicyEvaluationError(err) => {
+                                err.policy_id().clone()
+                            }
+                        })
+                        .sorted()
+                        .collect(),
+                )
+            })
+            .collect(),
+    )
+}
+
+/// Replaces every counterexample in `results` by a minimal one that conforms to `schema` and has
+/// the same outcome for `policysets`
+fn minimize_cexs(results: &mut [CheckResult], schema: &Schema, policysets: &[&PolicySet]) {
+    for cex in results.iter_mut().flat_map(|result| result.cexs.iter_mut()) {
+        let Some(outcome) = authorization_outcome(cex, policysets) else {
+            continue;
+        };
+        *cex = cex.clone().minimize(Some(schema), |candidate| {
+            authorization_outcome(candidate, policysets).as_ref() == Some(&outcome)
+        });
+    }
+}
+
 /***************************************************************************************************
  * Functions to pretty print results
  ***************************************************************************************************/
@@ -370,6 +1643,93 @@ fn print_check_never_errors_results(
     sig_widths.print_footer(res_width);
 }
 
+fn print_check_always_matches_results(
+    results: &[bool],
+    req_envs: &[RequestEnv],
+    open_req_env: &OpenRequestEnv,
+) {
+    if results.iter().all(|r| *r) {
+        if open_req_env.is_any() {
+            println!("Policy matches all requests")
+        } else {
+            println!("Policy matches all requests when {}", open_req_env)
+        }
+    } else if results.iter().all(|r| !*r) {
+        if open_req_env.is_any() {
+            println!("Policy does not match all requests for all request signatures")
+        } else {
+            println!("Policy does not match all requests when {}", open_req_env)
+        }
+    } else if open_req_env.is_any() {
+        println!("Policy matches all requests for some request signatures")
+    } else {
+        println!(
+            "Policy matches all requests for some request signatures where {}",
+            open_req_env
+        )
+    }
+
+    println!();
+    println!("Per request signature results:");
+
+    let sig_widths = SigWidths::from_req_envs(req_envs);
+    let res_width = 18; // Does not Match All
+
+    sig_widths.print_header(res_width, "Result");
+    for (req_env, result) in zip(req_envs.iter(), results.iter()) {
+        let result = if *result {
+            "Matches All"
+        } else {
+            "Does not Match All"
+        };
+        sig_widths.print_row(req_env, res_width, result);
+    }
+    sig_widths.print_footer(res_width);
+}
+
+fn print_check_never_matches_results(
+    results: &[bool],
+    req_envs: &[RequestEnv],
+    open_req_env: &OpenRequestEnv,
+) {
+    if results.iter().all(|r| *r) {
+        if open_req_env.is_any() {
+            println!("Policy never matches")
+        } else {
+            println!("Policy never matches when {}", open_req_env)
+        }
+    } else if results.iter().all(|r| !*r) {
+        if open_req_env.is_any() {
+            println!("Policy can match for all request signatures")
+        } else {
+            println!(
+                "Policy can match for all request signatures where {}",
+                open_req_env
+            )
+        }
+    } else if open_req_env.is_any() {
+        println!("Policy can match for some request signatures")
+    } else {
+        println!(
+            "Policy can match for some request signatures where {}",
+            open_req_env
+        )
+    }
+
+    println!();
+    println!("Per request signature results:");
+
+    let sig_widths = SigWidths::from_req_envs(req_envs);
+    let res_width = 13; // Never Matches
+
+    sig_widths.print_header(res_width, "Result");
+    for (req_env, result) in zip(req_envs.iter(), results.iter()) {
+        let result = if *result { "Never Matches" } else { "Matches" };
+        sig_widths.print_row(req_env, res_width, result);
+    }
+    sig_widths.print_footer(res_width);
+}
+
 fn print_check_always_allows_results(
     results: &[bool],
     req_envs: &[RequestEnv],
@@ -593,3 +1953,179 @@ fn print_check_disjoint_results(
     }
     sig_widths.print_footer(res_width);
 }
+
+fn print_check_matches_equivalent_results(
+    results: &[bool],
+    req_envs: &[RequestEnv],
+    open_req_env: &OpenRequestEnv,
+) {
+    if results.iter().all(|r| *r) {
+        if open_req_env.is_any() {
+            println!("Policies are equivalent")
+        } else {
+            println!("Policies are equivalent when {}", open_req_env)
+        }
+    } else if results.iter().all(|r| !*r) {
+        if open_req_env.is_any() {
+            println!("Policies are not equivalent")
+        } else {
+            println!(
+                "Policies are not equivalent for all requests where {}",
+                open_req_env
+            )
+        }
+    } else if open_req_env.is_any() {
+        println!("Policies are equivalent for some request signatures")
+    } else {
+        println!(
+            "Policies are equivalent for some request signatures where {}",
+            open_req_env
+        )
+    }
+
+    println!();
+    println!("Per request signature results:");
+
+    let sig_widths = SigWidths::from_req_envs(req_envs);
+    let res_width = 14; // Not Equivalent
+
+    sig_widths.print_header(res_width, "Result");
+    for (req_env, result) in zip(req_envs.iter(), results.iter()) {
+        let result = if *result {
+            "Equivalent"
+        } else {
+            "Not Equivalent"
+        };
+        sig_widths.print_row(req_env, res_width, result);
+    }
+    sig_widths.print_footer(res_width);
+}
+
+fn print_check_matches_implies_results(
+    results: &[bool],
+    req_envs: &[RequestEnv],
+    open_req_env: &OpenRequestEnv,
+) {
+    if results.iter().all(|r| *r) {
+        if open_req_env.is_any() {
+            println!("policy1 implies policy2")
+        } else {
+            println!("policy1 implies policy2 when {}", open_req_env)
+        }
+    } else if results.iter().all(|r| !*r) {
+        if open_req_env.is_any() {
+            println!("policy1 does not imply policy2")
+        } else {
+            println!(
+                "policy1 does not imply policy2 for all requests where {}",
+                open_req_env
+            )
+        }
+    } else if open_req_env.is_any() {
+        println!("policy1 implies policy2 for some request signatures")
+    } else {
+        println!(
+            "policy1 implies policy2 for some request signatures where {}",
+            open_req_env
+        )
+    }
+
+    println!();
+    println!("Per request signature results:");
+
+    let sig_widths = SigWidths::from_req_envs(req_envs);
+    let res_width = 14; // Does Not Imply
+
+    sig_widths.print_header(res_width, "Result");
+    for (req_env, result) in zip(req_envs.iter(), results.iter()) {
+        let result = if *result { "Implies" } else { "Does Not Imply" };
+        sig_widths.print_row(req_env, res_width, result);
+    }
+    sig_widths.print_footer(res_width);
+}
+
+fn print_check_matches_disjoint_results(
+    results: &[bool],
+    req_envs: &[RequestEnv],
+    open_req_env: &OpenRequestEnv,
+) {
+    if results.iter().all(|r| *r) {
+        if open_req_env.is_any() {
+            println!("policy1 is disjoint with policy2")
+        } else {
+            println!("policy1 is disjoint with policy2 when {}", open_req_env)
+        }
+    } else if results.iter().all(|r| !*r) {
+        if open_req_env.is_any() {
+            println!("policy1 is not disjoint with policy2")
+        } else {
+            println!(
+                "policy1 is not disjoint with policy2 for all requests where {}",
+                open_req_env
+            )
+        }
+    } else if open_req_env.is_any() {
+        println!("policy1 is disjoint with policy2 for some request signatures")
+    } else {
+        println!(
+            "policy1 is disjoint with policy2 for some request signatures where {}",
+            open_req_env
+        )
+    }
+
+    println!();
+    println!("Per request signature results:");
+
+    let sig_widths = SigWidths::from_req_envs(req_envs);
+    let res_width = 12; // Not Disjoint
+
+    sig_widths.print_header(res_width, "Result");
+    for (req_env, result) in zip(req_envs.iter(), results.iter()) {
+        let result = if *result { "Disjoint" } else { "Not Disjoint" };
+        sig_widths.print_row(req_env, res_width, result);
+    }
+    sig_widths.print_footer(res_width);
+}
+
+fn print_check_property_results(
+    results: &[bool],
+    req_envs: &[RequestEnv],
+    open_req_env: &OpenRequestEnv,
+) {
+    if results.iter().all(|r| *r) {
+        if open_req_env.is_any() {
+            println!("PolicySet satisfies the property")
+        } else {
+            println!("PolicySet satisfies the property when {}", open_req_env)
+        }
+    } else if results.iter().all(|r| !*r) {
+        if open_req_env.is_any() {
+            println!("PolicySet does not satisfy the property")
+        } else {
+            println!(
+                "PolicySet does not satisfy the property for all requests where {}",
+                open_req_env
+            )
+        }
+    } else if open_req_env.is_any() {
+        println!("PolicySet satisfies the property for some request signatures")
+    } else {
+        println!(
+            "PolicySet satisfies the property for some request signatures where {}",
+            open_req_env
+        )
+    }
+
+    println!();
+    println!("Per request signature results:");
+
+    let sig_widths = SigWidths::from_req_envs(req_envs);
+    let res_width = 13; // Does Not Hold
+
+    sig_widths.print_header(res_width, "Result");
+    for (req_env, result) in zip(req_envs.iter(), results.iter()) {
+        let result = if *result { "Holds" } else { "Does Not Hold" };
+        sig_widths.print_row(req_env, res_width, result);
+    }
+    sig_widths.print_footer(res_width);
+}
@@ -14,17 +14,23 @@
  * limitations under the License.
  */
 use crate::analysis::{AnalyzePolicyFindings, PerSigFindings, VacuityResult};
-use crate::cli_enums::{ContextArg, RequestArgsEnum, ValidationMode};
+use crate::cli_enums::{ContextArg, PartialRequestArgs, RequestArgsEnum, ValidationMode};
 use crate::err::{ContentType, EntityType, ExecError, RequestElement};
+use cedar_lean_ffi::{CedarLeanFfi, LeanSchema, TimedResult};
 use cedar_policy::{
-    Context, Entities, EntityId, EntityTypeName, EntityUid, Expression, Policy, PolicyId,
-    PolicySet, Request, RequestEnv, Schema,
+    Context, Entities, Entity, EntityId, EntityLoader, EntityTypeName, EntityUid, Expression,
+    PartialEntities, PartialEntityUid, PartialRequest, Policy, PolicyId, PolicySet, Request,
+    RequestEnv, Schema, SlotId,
 };
 use itertools::Itertools;
 use miette::WrapErr;
-use serde::Serialize;
+use prettytable::{Attr, Cell, Row, Table};
+use serde::{Deserialize, Serialize};
 use serde_json::{Value, from_str};
-use std::collections::HashSet;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::time::Duration;
 use std::{fs::read_to_string, path::PathBuf, str::FromStr};
 
 /// A struct reprensting which request environments to restrict the analysis to
@@ -264,6 +270,21 @@ pub fn parse_policy(fname: &PathBuf) -> Result<Policy, ExecError> {
     }
 }
 
+/// Auxilary function that parses a Policy, or, when `policy_id` is provided, parses a PolicySet
+/// and selects the Policy with the given id
+pub fn parse_policy_with_id(fname: &PathBuf, policy_id: Option<&str>) -> Result<Policy, ExecError> {
+    match policy_id {
+        None => parse_policy(fname),
+        Some(policy_id) => parse_policyset(fname)?
+            .policy(&PolicyId::new(policy_id))
+            .cloned()
+            .ok_or_else(|| ExecError::PolicyNotFoundError {
+                policy_id: policy_id.to_string(),
+                file_name: fname.to_path_buf(),
+            }),
+    }
+}
+
 fn rename_from_id_annotation_policy(policy: Policy) -> Policy {
     match policy.annotation("id") {
         None => policy,
@@ -274,10 +295,19 @@ fn rename_from_id_annotation_policy(policy: Policy) -> Policy {
     }
 }
 
-/// Auxilary function that parses a PolicySet, simple wrapper around cedar::PolicySet::from_str
+/// Auxilary function that parses a PolicySet, simple wrapper around cedar::PolicySet::from_str,
+/// or cedar::PolicySet::from_json_str for `.json` files
 pub fn parse_policyset(fname: &PathBuf) -> Result<PolicySet, ExecError> {
+    let parse =
+        |policyset_text: &str| -> Result<PolicySet, Box<dyn std::error::Error + Send + Sync>> {
+            if is_json_file(fname) {
+                Ok(PolicySet::from_json_str(policyset_text)?)
+            } else {
+                Ok(PolicySet::from_str(policyset_text)?)
+            }
+        };
     match read_to_string(fname) {
-        Ok(policyset_text) => match PolicySet::from_str(policyset_text.as_str()) {
+        Ok(policyset_text) => match parse(&policyset_text) {
             Ok(policyset) => match rename_from_id_annotation_policyset(policyset) {
                 Ok(pset) => Ok(pset),
                 Err(err) => Err(ExecError::RenameIdError {
@@ -289,7 +319,7 @@ pub fn parse_policyset(fname: &PathBuf) -> Result<PolicySet, ExecError> {
             Err(parse_err) => Err(ExecError::ParseError {
                 content_type: ContentType::PolicySet,
                 file_name: fname.to_path_buf(),
-                error: Box::new(parse_err),
+                error: parse_err,
             }),
         },
         Err(read_error) => Err(ExecError::FileReadError {
@@ -300,58 +330,228 @@ pub fn parse_policyset(fname: &PathBuf) -> Result<PolicySet, ExecError> {
     }
 }
 
+/// Auxilary function that parses a PolicySet and, when `links_file` is provided, links its
+/// templates with the template-link instantiations in `links_file`
+pub fn parse_linked_policyset(
+    fname: &PathBuf,
+    links_file: Option<&PathBuf>,
+) -> Result<PolicySet, ExecError> {
+    let mut policyset = parse_policyset(fname)?;
+    if let Some(links_file) = links_file {
+        for link in parse_template_links(links_file)? {
+            link.add_to(&mut policyset, links_file)?;
+        }
+    }
+    Ok(policyset)
+}
+
+/// A template-link instantiation, in the format of the `cedar` CLI's `--template-linked` file
+#[derive(Debug, Deserialize)]
+struct TemplateLink {
+    /// Id of the template to link
+    template_id: String,
+    /// Id of the template-linked policy
+    link_id: String,
+    /// The entity each slot (`?principal` or `?resource`) is linked to
+    args: HashMap<String, String>,
+}
+
+impl TemplateLink {
+    fn add_to(self, policyset: &mut PolicySet, links_file: &PathBuf) -> Result<(), ExecError> {
+        let link_error = |error: Box<dyn std::error::Error + Send + Sync>| ExecError::LinkError {
+            link_id: self.link_id.clone(),
+            file_name: links_file.to_path_buf(),
+            error,
+        };
+        let mut vals = HashMap::new();
+        for (slot, uid) in self.args.iter() {
+            let slot = match slot.as_str() {
+                "?principal" => SlotId::principal(),
+                "?resource" => SlotId::resource(),
+                _ => {
+                    return Err(link_error(
+                        format!("invalid slot `{slot}`, expected `?principal` or `?resource`")
+                            .into(),
+                    ));
+                }
+            };
+            let uid = EntityUid::from_str(uid).map_err(|err| link_error(Box::new(err)))?;
+            vals.insert(slot, uid);
+        }
+        policyset
+            .link(
+                PolicyId::new(&self.template_id),
+                PolicyId::new(&self.link_id),
+                vals,
+            )
+            .map_err(|err| link_error(Box::new(err)))
+    }
+}
+
+/// Auxilary function that parses the template-link instantiations in `fname`
+fn parse_template_links(fname: &PathBuf) -> Result<Vec<TemplateLink>, ExecError> {
+    let links_text = read_to_string(fname).map_err(|err| ExecError::FileReadError {
+        content_type: ContentType::TemplateLinks,
+        file_name: fname.to_path_buf(),
+        error: Box::new(err),
+    })?;
+    serde_json::from_str(&links_text).map_err(|err| ExecError::ParseError {
+        content_type: ContentType::TemplateLinks,
+        file_name: fname.to_path_buf(),
+        error: Box::new(err),
+    })
+}
+
+/// The PolicySet containing only `policy` (and its template, if it is a template-linked policy of
+/// `policyset`)
+pub(crate) fn singleton_policyset(
+    policyset: &PolicySet,
+    policy: &Policy,
+) -> Result<PolicySet, ExecError> {
+    let into_policyset_error =
+        |err: cedar_policy::PolicySetError| ExecError::PolicyIntoPolicySetError {
+            error: Box::new(err),
+        };
+    match (policy.template_id(), policy.template_links()) {
+        (Some(template_id), Some(links)) => {
+            let mut pset = PolicySet::new();
+            if let Some(template) = policyset.template(template_id) {
+                pset.add_template(template.clone())
+                    .map_err(into_policyset_error)?;
+            }
+            pset.link(template_id.clone(), policy.id().clone(), links)
+                .map_err(into_policyset_error)?;
+            Ok(pset)
+        }
+        _ => PolicySet::from_policies([policy.clone()]).map_err(into_policyset_error),
+    }
+}
+
 fn rename_from_id_annotation_policyset(ps: PolicySet) -> miette::Result<PolicySet> {
     let mut new_ps = PolicySet::new();
-    let t_iter = ps.templates().map(|t| match t.annotation("id") {
-        None => Ok(t.clone()),
-        Some(anno) => anno.parse().map(|a| t.new_id(a)),
-    });
-    for t in t_iter {
-        let template = t.unwrap_or_else(|never| match never {});
+    let mut template_ids = HashMap::new();
+    for t in ps.templates() {
+        let template = match t.annotation("id") {
+            None => Ok(t.clone()),
+            Some(anno) => anno.parse().map(|a| t.new_id(a)),
+        }
+        .unwrap_or_else(|never| match never {});
+        template_ids.insert(t.id().clone(), template.id().clone());
         new_ps
             .add_template(template)
             .wrap_err("failed to add template to policy set")?;
     }
-    let p_iter = ps.policies().map(|p| match p.annotation("id") {
-        None => Ok(p.clone()),
-        Some(anno) => anno.parse().map(|a| p.new_id(a)),
-    });
+    let p_iter = ps
+        .policies()
+        .filter(|p| p.is_static())
+        .map(|p| match p.annotation("id") {
+            None => Ok(p.clone()),
+            Some(anno) => anno.parse().map(|a| p.new_id(a)),
+        });
     for p in p_iter {
         let policy = p.unwrap_or_else(|never| match never {});
         new_ps
             .add(policy)
             .wrap_err("failed to add policy to policy set")?;
     }
+    // Template-linked policies (e.g., the `templateLinks` of a JSON PolicySet) are linked again,
+    // to the renamed templates
+    for p in ps.policies().filter(|p| !p.is_static()) {
+        if let (Some(template_id), Some(vals)) = (p.template_id(), p.template_links()) {
+            new_ps
+                .link(template_ids[template_id].clone(), p.id().clone(), vals)
+                .wrap_err("failed to link template-linked policy")?;
+        }
+    }
     Ok(new_ps)
 }
 
+/// Line/column span (1-based, end inclusive) of a policy in its source file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct PolicySpan {
+    pub(crate) start_line: usize,
+    pub(crate) start_column: usize,
+    pub(crate) end_line: usize,
+    pub(crate) end_column: usize,
+}
+
+impl PolicySpan {
+    /// Convert the byte range `offset..offset + len` of `src` into lines and (character) columns
+    fn from_byte_range(src: &str, offset: usize, len: usize) -> Self {
+        let line_col = |pos: usize| {
+            let before = &src[..pos];
+            let line_start = before.rfind('\n').map_or(0, |ind| ind + 1);
+            (
+                before.matches('\n').count() + 1,
+                before[line_start..].chars().count() + 1,
+            )
+        };
+        let (start_line, start_column) = line_col(offset);
+        let (end_line, end_column) = line_col(offset + len.saturating_sub(1));
+        Self {
+            start_line,
+            start_column,
+            end_line,
+            end_column,
+        }
+    }
+}
+
+/// Auxilary function that locates every static policy of the PolicySet in `fname`.
+/// Policies are keyed by the id they are given by `parse_policyset` (i.e., after `@id` renaming).
+pub(crate) fn parse_policyset_spans(
+    fname: &PathBuf,
+) -> Result<HashMap<PolicyId, PolicySpan>, ExecError> {
+    // The JSON format does not keep source locations
+    if is_json_file(fname) {
+        return Ok(HashMap::new());
+    }
+    let policyset_text = read_to_string(fname).map_err(|err| ExecError::FileReadError {
+        content_type: ContentType::PolicySet,
+        file_name: fname.to_path_buf(),
+        error: Box::new(err),
+    })?;
+    let parse_error = |err: Box<dyn std::error::Error + Send + Sync>| ExecError::ParseError {
+        content_type: ContentType::PolicySet,
+        file_name: fname.to_path_buf(),
+        error: err,
+    };
+    // Both parsers assign the same default ids (`policy0`, `policy1`, ...), so the
+    // core AST (which keeps source locations) can be matched with the renamed policies
+    let spans: HashMap<String, PolicySpan> =
+        cedar_policy_core::parser::parse_policyset(&policyset_text)
+            .map_err(|err| parse_error(Box::new(err)))?
+            .policies()
+            .filter_map(|policy| {
+                policy.loc().map(|loc| {
+                    (
+                        policy.id().to_string(),
+                        PolicySpan::from_byte_range(
+                            &policyset_text,
+                            loc.span.offset(),
+                            loc.span.len(),
+                        ),
+                    )
+                })
+            })
+            .collect();
+    Ok(PolicySet::from_str(&policyset_text)
+        .map_err(|err| parse_error(Box::new(err)))?
+        .policies()
+        .filter_map(|policy| {
+            let span = spans.get(&policy.id().to_string())?;
+            let policy = rename_from_id_annotation_policy(policy.clone());
+            Some((policy.id().clone(), *span))
+        })
+        .collect())
+}
+
 /// Auxilary function that parses a Schema
 /// if the schema-file name ends in .json use JSON format, otherwise
 /// use the cedar-schema format.
 pub fn parse_schema(fname: &PathBuf) -> Result<Schema, ExecError> {
     match read_to_string(fname) {
-        Ok(schema_text) => {
-            let is_json_schema = fname.extension().is_some_and(|ext| ext == "json");
-            if is_json_schema {
-                match Schema::from_json_str(schema_text.as_str()) {
-                    Ok(schema) => Ok(schema),
-                    Err(schema_err) => Err(ExecError::ParseError {
-                        content_type: ContentType::SchemaJSON,
-                        file_name: fname.to_path_buf(),
-                        error: Box::new(schema_err),
-                    }),
-                }
-            } else {
-                match Schema::from_str(schema_text.as_str()) {
-                    Ok(schema) => Ok(schema),
-                    Err(schema_err) => Err(ExecError::ParseError {
-                        content_type: ContentType::Schema,
-                        file_name: fname.to_path_buf(),
-                        error: Box::new(schema_err),
-                    }),
-                }
-            }
-        }
+        Ok(schema_text) => parse_schema_text(&schema_text, fname),
         Err(read_error) => Err(ExecError::FileReadError {
             content_type: ContentType::Schema,
             file_name: fname.to_path_buf(),
@@ -360,6 +560,85 @@ pub fn parse_schema(fname: &PathBuf) -> Result<Schema, ExecError> {
     }
 }
 
+/// Auxilary function that parses a Schema from `schema_text`, which was read from `fname`
+pub(crate) fn parse_schema_text(schema_text: &str, fname: &PathBuf) -> Result<Schema, ExecError> {
+    if is_json_file(fname) {
+        match Schema::from_json_str(schema_text) {
+            Ok(schema) => Ok(schema),
+            Err(schema_err) => Err(ExecError::ParseError {
+                content_type: ContentType::SchemaJSON,
+                file_name: fname.to_path_buf(),
+                error: Box::new(schema_err),
+            }),
+        }
+    } else {
+        match Schema::from_str(schema_text) {
+            Ok(schema) => Ok(schema),
+            Err(schema_err) => Err(ExecError::ParseError {
+                content_type: ContentType::Schema,
+                file_name: fname.to_path_buf(),
+                error: Box::new(schema_err),
+            }),
+        }
+    }
+}
+
+/// Whether the schema or PolicySet in `fname` is in JSON format (rather than the Cedar format)
+pub(crate) fn is_json_file(fname: &PathBuf) -> bool {
+    fname.extension().is_some_and(|ext| ext == "json")
+}
+
+/// Schemas, each with its Lean schema object, keyed by a hash of the schema file contents
+#[derive(Default)]
+pub(crate) struct SchemaCache {
+    schemas: HashMap<u64, CachedSchema>,
+}
+
+pub(crate) struct CachedSchema {
+    pub(crate) schema: Schema,
+    pub(crate) lean_schema: LeanSchema,
+}
+
+impl SchemaCache {
+    /// The schema in `fname`, which is only parsed and loaded into Lean if its contents changed
+    pub(crate) fn get(
+        &mut self,
+        lean_ffi: &CedarLeanFfi,
+        fname: &PathBuf,
+    ) -> Result<&CachedSchema, ExecError> {
+        let schema_text = read_to_string(fname).map_err(|err| ExecError::FileReadError {
+            content_type: ContentType::Schema,
+            file_name: fname.to_path_buf(),
+            error: Box::new(err),
+        })?;
+        let mut hasher = DefaultHasher::new();
+        (is_json_file(fname), &schema_text).hash(&mut hasher);
+        match self.schemas.entry(hasher.finish()) {
+            Entry::Occupied(entry) => Ok(entry.into_mut()),
+            Entry::Vacant(entry) => {
+                let schema = parse_schema_text(&schema_text, fname)?;
+                let lean_schema = lean_ffi.load_lean_schema_object(&schema)?;
+                Ok(entry.insert(CachedSchema {
+                    schema,
+                    lean_schema,
+                }))
+            }
+        }
+    }
+
+    /// The schema in `fname`, if provided
+    pub(crate) fn get_optional(
+        &mut self,
+        lean_ffi: &CedarLeanFfi,
+        fname: Option<&PathBuf>,
+    ) -> Result<Option<&Schema>, ExecError> {
+        match fname {
+            Some(fname) => Ok(Some(&self.get(lean_ffi, fname)?.schema)),
+            None => Ok(None),
+        }
+    }
+}
+
 /// Auxillary function used to parse a file containing Cedar Entities
 pub fn parse_entities(fname: &PathBuf, schema: Option<&Schema>) -> Result<Entities, ExecError> {
     match read_to_string(fname) {
@@ -379,6 +658,30 @@ pub fn parse_entities(fname: &PathBuf, schema: Option<&Schema>) -> Result<Entiti
     }
 }
 
+/// Auxillary function used to parse a file containing partial Cedar Entities
+pub fn parse_partial_entities(
+    fname: &PathBuf,
+    schema: &Schema,
+) -> Result<PartialEntities, ExecError> {
+    let parse_error = |error: Box<dyn std::error::Error + Send + Sync>| ExecError::ParseError {
+        content_type: ContentType::Entities,
+        file_name: fname.to_path_buf(),
+        error,
+    };
+    match read_to_string(fname) {
+        Ok(entities_json_str) => {
+            let value =
+                from_str::<Value>(&entities_json_str).map_err(|e| parse_error(Box::new(e)))?;
+            PartialEntities::from_json_value(value, schema).map_err(|e| parse_error(Box::new(e)))
+        }
+        Err(read_error) => Err(ExecError::FileReadError {
+            content_type: ContentType::Entities,
+            file_name: fname.to_path_buf(),
+            error: Box::new(read_error),
+        }),
+    }
+}
+
 /// Auxillary function used to parse a file containing a Cedar Expression
 pub fn parse_expression(fname: &PathBuf) -> Result<Expression, ExecError> {
     match read_to_string(fname) {
@@ -431,7 +734,10 @@ impl ContextArg {
 }
 
 /// Auxillary function that converts a string to an Cedar EntityUid
-fn parse_entity_uid(input_str: String, element: RequestElement) -> Result<EntityUid, ExecError> {
+pub(crate) fn parse_entity_uid(
+    input_str: String,
+    element: RequestElement,
+) -> Result<EntityUid, ExecError> {
     match EntityUid::from_str(&input_str) {
         Ok(euid) => Ok(euid),
         Err(e) => Err(ExecError::RequestError {
@@ -496,6 +802,170 @@ fn request_from_json_value(
     }
 }
 
+/// Auxillary function used to parse a file containing one JSON request (principal, action, resource, context) per line
+pub fn parse_requests_jsonl(
+    fname: &PathBuf,
+    schema: Option<&Schema>,
+) -> Result<Vec<Request>, ExecError> {
+    let requests_str = read_to_string(fname).map_err(|e| ExecError::FileReadError {
+        content_type: ContentType::Request,
+        file_name: fname.to_path_buf(),
+        error: Box::new(e),
+    })?;
+    requests_str
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| parse_request_json(line, fname, schema))
+        .collect()
+}
+
+/// Auxillary function used to parse a single JSON request (principal, action, resource, context) read from `fname`
+pub fn parse_request_json(
+    json_str: &str,
+    fname: &PathBuf,
+    schema: Option<&Schema>,
+) -> Result<Request, ExecError> {
+    match from_str::<Value>(json_str) {
+        Ok(v) => request_from_json_value(v, fname.to_path_buf(), schema),
+        Err(e) => Err(ExecError::ParseError {
+            content_type: ContentType::Request,
+            file_name: fname.to_path_buf(),
+            error: Box::new(e),
+        }),
+    }
+}
+
+/// An entity store read from either a directory of JSON files (each holding an entity or an array
+/// of entities) or a JSONL file (holding an entity per line). Entities are only parsed against the
+/// schema when they are loaded, and the loader round-trips are counted.
+pub struct FileEntityLoader<'a> {
+    schema: &'a Schema,
+    store: HashMap<EntityUid, Value>,
+    round_trips: usize,
+    loaded: HashMap<EntityUid, Entity>,
+    error: Option<ExecError>,
+}
+
+impl<'a> FileEntityLoader<'a> {
+    pub fn new(path: &PathBuf, schema: &'a Schema) -> Result<Self, ExecError> {
+        let read_error = |file_name: &PathBuf, error: std::io::Error| ExecError::FileReadError {
+            content_type: ContentType::Entities,
+            file_name: file_name.to_path_buf(),
+            error: Box::new(error),
+        };
+        let parse_error = |file_name: &PathBuf, error: Box<dyn std::error::Error + Send + Sync>| {
+            ExecError::ParseError {
+                content_type: ContentType::Entities,
+                file_name: file_name.to_path_buf(),
+                error,
+            }
+        };
+        let mut entity_jsons = Vec::new();
+        if path.is_dir() {
+            let files = std::fs::read_dir(path)
+                .map_err(|e| read_error(path, e))?
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| read_error(path, e))?;
+            for file in files
+                .into_iter()
+                .filter(|file| file.extension().is_some_and(|ext| ext == "json"))
+                .sorted()
+            {
+                let json_str = read_to_string(&file).map_err(|e| read_error(&file, e))?;
+                match from_str::<Value>(&json_str).map_err(|e| parse_error(&file, Box::new(e)))? {
+                    Value::Array(values) => {
+                        entity_jsons.extend(values.into_iter().map(|v| (file.clone(), v)))
+                    }
+                    value => entity_jsons.push((file, value)),
+                }
+            }
+        } else {
+            let jsonl_str = read_to_string(path).map_err(|e| read_error(path, e))?;
+            for line in jsonl_str.lines().filter(|line| !line.trim().is_empty()) {
+                let value = from_str::<Value>(line).map_err(|e| parse_error(path, Box::new(e)))?;
+                entity_jsons.push((path.clone(), value));
+            }
+        }
+        let mut store = HashMap::new();
+        for (file, value) in entity_jsons {
+            let uid = value
+                .get("uid")
+                .cloned()
+                .ok_or_else(|| ExecError::ParseJsonError {
+                    content_type: ContentType::Entities,
+                    file_name: file.clone(),
+                })?;
+            let uid = EntityUid::from_json(uid).map_err(|e| parse_error(&file, Box::new(e)))?;
+            match store.entry(uid) {
+                Entry::Occupied(entry) => {
+                    return Err(ExecError::DuplicateEntityError {
+                        uid: entry.key().to_string(),
+                        file_name: file,
+                    });
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(value);
+                }
+            }
+        }
+        Ok(Self {
+            schema,
+            store,
+            round_trips: 0,
+            loaded: HashMap::new(),
+            error: None,
+        })
+    }
+
+    /// Forget the entities loaded, round-trips made and load error so far, e.g., before the next request
+    pub fn reset(&mut self) {
+        self.round_trips = 0;
+        self.loaded.clear();
+        self.error = None;
+    }
+
+    /// The number of loader round-trips made since the last `reset`
+    pub fn round_trips(&self) -> usize {
+        self.round_trips
+    }
+
+    /// The entities loaded since the last `reset`, or the first error encountered while loading them
+    pub fn loaded_entities(&mut self) -> Result<Entities, ExecError> {
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
+        Entities::from_entities(self.loaded.values().cloned(), Some(self.schema))
+            .map_err(|e| ExecError::InternalAnalysisError { error: Box::new(e) })
+    }
+}
+
+impl EntityLoader for FileEntityLoader<'_> {
+    fn load_entities(&mut self, uids: &HashSet<EntityUid>) -> HashMap<EntityUid, Option<Entity>> {
+        self.round_trips += 1;
+        uids.iter()
+            .map(|uid| {
+                let entity = self.store.get(uid).and_then(|value| {
+                    match Entity::from_json_value(value.clone(), Some(self.schema)) {
+                        Ok(entity) => Some(entity),
+                        Err(e) => {
+                            self.error.get_or_insert(ExecError::EntityLoadError {
+                                uid: uid.to_string(),
+                                error: Box::new(e),
+                            });
+                            None
+                        }
+                    }
+                });
+                if let Some(entity) = &entity {
+                    self.loaded.insert(uid.clone(), entity.clone());
+                }
+                (uid.clone(), entity)
+            })
+            .collect()
+    }
+}
+
 impl RequestArgsEnum {
     /// A function that parses a RequestArgEnum into a Cedar Request struct
     pub fn parse(self, schema: Option<&Schema>) -> Result<Request, ExecError> {
@@ -525,6 +995,71 @@ impl RequestArgsEnum {
     }
 }
 
+/// Auxillary function that converts a string to a Cedar EntityTypeName
+pub(crate) fn parse_entity_type(
+    input_str: String,
+    entity_type: EntityType,
+) -> Result<EntityTypeName, ExecError> {
+    match EntityTypeName::from_str(&input_str) {
+        Ok(type_name) => Ok(type_name),
+        Err(e) => Err(ExecError::EntityTypeError {
+            entity_type,
+            input_str,
+            error: Box::new(e),
+        }),
+    }
+}
+
+/// Auxillary function that converts either an entity uid or, if its id is unknown, an entity type
+/// into a Cedar PartialEntityUid
+fn parse_partial_entity_uid(
+    uid: Option<String>,
+    type_name: Option<String>,
+    element: RequestElement,
+    err_type: EntityType,
+) -> Result<PartialEntityUid, ExecError> {
+    match (uid, type_name) {
+        (Some(uid), _) => Ok(PartialEntityUid::from_concrete(parse_entity_uid(
+            uid, element,
+        )?)),
+        (None, Some(type_name)) => Ok(PartialEntityUid::new(
+            parse_entity_type(type_name, err_type)?,
+            None,
+        )),
+        (None, None) => panic!(
+            "Error parsing args. Either an entity or an entity type is required for the principal and resource"
+        ),
+    }
+}
+
+impl PartialRequestArgs {
+    /// Parses the CLI arguments into a Cedar PartialRequest, where the context is unknown unless provided
+    pub fn parse(self, schema: &Schema) -> Result<PartialRequest, ExecError> {
+        let principal = parse_partial_entity_uid(
+            self.principal,
+            self.principal_type,
+            RequestElement::Principal,
+            EntityType::PrincipalTypeName,
+        )?;
+        let action = parse_entity_uid(self.action, RequestElement::Action)?;
+        let resource = parse_partial_entity_uid(
+            self.resource,
+            self.resource_type,
+            RequestElement::Resource,
+            EntityType::ResourceTypeName,
+        )?;
+        let context = match (self.context, self.context_file) {
+            (Some(json_str), _) => Some(ContextArg::FromString { json_str }),
+            (_, Some(file_name)) => Some(ContextArg::FromFile { file_name }),
+            (None, None) => None,
+        }
+        .map(|context| context.parse(Some((schema, &action))))
+        .transpose()?;
+        PartialRequest::new(principal, action, resource, context, schema)
+            .map_err(|e| ExecError::RequestValidationError { error: Box::new(e) })
+    }
+}
+
 /// Convert from our ValidationMode enum to Cedar ValidationMode enum
 impl ValidationMode {
     pub fn to_cedar(self) -> cedar_policy::ValidationMode {
@@ -552,6 +1087,154 @@ impl RequestEnvSer {
     }
 }
 
+impl std::fmt::Display for RequestEnvSer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "(principal: {}, action: {}, resource: {})",
+            self.principal_type, self.action_uid, self.resource_type
+        )
+    }
+}
+
+/// Split the result of a `*_timed` Lean call from the time it took
+pub(crate) fn split_timed<T>(timed: TimedResult<T>) -> (T, Duration) {
+    // Lean reports durations in nanoseconds
+    let duration = Duration::from_nanos(timed.duration().try_into().unwrap_or(u64::MAX));
+    (timed.take_result(), duration)
+}
+
+/// A step of a command, whose time spent in Lean is reported with `--timings`
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum TimedStep {
+    ReqEnv(RequestEnvSer),
+    Policy(PolicyId),
+    PolicyPair(PolicyId, PolicyId),
+    PolicySet,
+    Removal(PolicyId),
+    Authorization,
+    Evaluation,
+    ExpectedOutput,
+    Validation,
+}
+
+impl std::fmt::Display for TimedStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ReqEnv(req_env) => write!(f, "RequestEnv {req_env}"),
+            Self::Policy(pid) => write!(f, "Policy {pid}"),
+            Self::PolicyPair(pid1, pid2) => write!(f, "Policies {pid1} and {pid2}"),
+            Self::PolicySet => write!(f, "PolicySet"),
+            Self::Removal(pid) => write!(f, "Removal of {pid}"),
+            Self::Authorization => write!(f, "Authorization"),
+            Self::Evaluation => write!(f, "Evaluation"),
+            Self::ExpectedOutput => write!(f, "Comparison with the expected output"),
+            Self::Validation => write!(f, "Validation"),
+        }
+    }
+}
+
+/// The time spent in Lean for each step of a command
+#[derive(Debug, Default)]
+pub(crate) struct Timings {
+    steps: Vec<(TimedStep, Duration)>,
+}
+
+impl Timings {
+    pub(crate) fn push(&mut self, step: TimedStep, duration: Duration) {
+        self.steps.push((step, duration));
+    }
+
+    pub(crate) fn total(&self) -> Duration {
+        self.steps.iter().map(|(_, duration)| *duration).sum()
+    }
+
+    /// Print the steps from slowest to fastest, followed by the total
+    pub(crate) fn print_table(&self) {
+        let mut table = Table::new();
+        // Print a nice header
+        table.add_row(Row::new(vec![
+            Cell::new("Step").with_style(Attr::Bold),
+            Cell::new("Time (ms)").with_style(Attr::Bold),
+        ]));
+        for (step, duration) in self.steps.iter().sorted_by(|(_, d1), (_, d2)| d2.cmp(d1)) {
+            table.add_row(Row::new(vec![
+                Cell::new(&step.to_string()),
+                Cell::new(&format!("{:.3}", millis(*duration))),
+            ]));
+        }
+        table.printstd();
+        println!("Total time in Lean: {:.3} ms", millis(self.total()));
+    }
+}
+
+pub(crate) fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// The `--json-output` of a command run with `--timings`
+#[derive(Serialize, Debug, Clone)]
+pub(crate) struct TimedOutputSer<T> {
+    pub(crate) results: T,
+    pub(crate) timings: TimingsSer,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub(crate) struct TimingsSer {
+    steps: Vec<TimedStepSer>,
+    total_ms: f64,
+}
+
+#[derive(Serialize, Debug, Clone)]
+struct TimedStepSer {
+    step: TimedStep,
+    duration_ms: f64,
+}
+
+impl From<&Timings> for TimingsSer {
+    fn from(timings: &Timings) -> Self {
+        TimingsSer {
+            steps: timings
+                .steps
+                .iter()
+                .map(|(step, duration)| TimedStepSer {
+                    step: step.clone(),
+                    duration_ms: millis(*duration),
+                })
+                .collect(),
+            total_ms: millis(timings.total()),
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub(crate) struct CheckResultSer {
+    req_env: RequestEnvSer,
+    /// Whether the check holds, or `null` if the solver could not decide it
+    holds: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    counterexample: Option<cedar_lean_ffi::Env>,
+    /// Any further (distinct) counterexamples, when more than one is requested
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    additional_counterexamples: Vec<cedar_lean_ffi::Env>,
+}
+
+impl CheckResultSer {
+    pub(crate) fn new(
+        req_env: &RequestEnv,
+        holds: Option<bool>,
+        counterexamples: &[cedar_lean_ffi::Env],
+    ) -> Self {
+        CheckResultSer {
+            req_env: RequestEnvSer::new(req_env),
+            holds,
+            counterexample: counterexamples.first().cloned(),
+            additional_counterexamples: counterexamples.iter().skip(1).cloned().collect(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 struct PolicySer {
     policy_id: PolicyId,
@@ -560,7 +1243,9 @@ struct PolicySer {
 
 impl PolicySer {
     fn new(policy_id: &PolicyId, policy_set: &PolicySet) -> Self {
-        let policy_str = policy_set.policy(policy_id).unwrap().to_cedar().unwrap();
+        let policy = policy_set.policy(policy_id).unwrap();
+        // `to_cedar` is `None` for template-linked policies, whose text has the slots filled in
+        let policy_str = policy.to_cedar().unwrap_or_else(|| policy.to_string());
         PolicySer {
             policy_id: policy_id.clone(),
             policy_str,
@@ -598,6 +1283,18 @@ struct EquivalentPolicies {
     equivalent_policies: Vec<PolicySer>,
 }
 
+#[derive(Debug, Clone, Serialize)]
+struct ErrorFinding {
+    policy: PolicySer,
+    example: cedar_lean_ffi::Env,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct UndecidedPolicies {
+    policy1: PolicySer,
+    policy2: PolicySer,
+}
+
 #[derive(Debug, Clone, Serialize)]
 struct VacuityFinding {
     policy: PolicySer,
@@ -611,6 +1308,15 @@ struct PerSigFindingsSer {
     permit_shadowed_by_permits: Vec<PermitShadowedByPermit>,
     forbid_shadowed_by_forbids: Vec<ForbidShadowedByForbids>,
     permit_overridden_by_forbids: Vec<PermitOverridenByForbids>,
+    /// Policies that error for every request their scope matches
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    always_errors: Vec<ErrorFinding>,
+    /// Policies that error for some (but not all) requests their scope matches
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    may_error: Vec<ErrorFinding>,
+    /// Pairs of policies whose relationship the solver could not decide
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    undecided: Vec<UndecidedPolicies>,
 }
 
 impl PerSigFindingsSer {
@@ -672,12 +1378,38 @@ impl PerSigFindingsSer {
                 }
             })
             .collect_vec();
+
+        let error_findings = |errors: &HashMap<PolicyId, cedar_lean_ffi::Env>| {
+            errors
+                .iter()
+                .sorted_by_key(|(pid, _)| pid.to_string())
+                .map(|(pid, env)| ErrorFinding {
+                    policy: PolicySer::new(pid, policy_set),
+                    example: env.clone(),
+                })
+                .collect_vec()
+        };
+        let always_errors = error_findings(&per_sig_findings.always_errors);
+        let may_error = error_findings(&per_sig_findings.may_error);
+        let undecided = per_sig_findings
+            .undecided
+            .iter()
+            .flat_map(|(pid, pid_set)| {
+                pid_set.iter().map(|opid| UndecidedPolicies {
+                    policy1: PolicySer::new(pid, policy_set),
+                    policy2: PolicySer::new(opid, policy_set),
+                })
+            })
+            .collect_vec();
         PerSigFindingsSer {
             req_env: per_sig_findings.req_env.clone(),
             equiv_classes,
             permit_shadowed_by_permits,
             forbid_shadowed_by_forbids,
             permit_overridden_by_forbids,
+            always_errors,
+            may_error,
+            undecided,
         }
     }
 }
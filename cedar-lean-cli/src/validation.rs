@@ -14,8 +14,138 @@
  * limitations under the License.
  */
 use crate::err::ExecError;
-use cedar_lean_ffi::{CedarLeanFfi, ValidationResponse};
+use crate::util::{
+    TimedOutputSer, TimedStep, Timings, TimingsSer, singleton_policyset, split_timed,
+};
+use cedar_lean_ffi::{CedarLeanFfi, FfiError, TimedResult, ValidationResponse};
 use cedar_policy::{Entities, PolicySet, Request, Schema, ValidationMode};
+use itertools::Itertools;
+use serde::Serialize;
+use std::time::Duration;
+
+/// A validation error, as reported by the `--json-output` of the validate commands
+#[derive(Debug, Clone, Serialize)]
+struct ValidationErrorSer {
+    /// The policy failing to validate, for PolicySet validation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    policy_id: Option<String>,
+    /// The kind of error reported by the lean_ffi backend (e.g., `attrNotFound`)
+    kind: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+}
+
+/// The outcome of a validate command, as reported by its `--json-output`
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct ValidationResultSer {
+    valid: bool,
+    errors: Vec<ValidationErrorSer>,
+}
+
+impl ValidationResultSer {
+    /// Print the result, together with the `timings` if any
+    fn print(self, timings: Option<&Timings>) {
+        let json = match timings {
+            Some(timings) => serde_json::to_string_pretty(&TimedOutputSer {
+                results: self,
+                timings: TimingsSer::from(timings),
+            }),
+            None => serde_json::to_string_pretty(&self),
+        }
+        .unwrap();
+        println!("{}", json);
+    }
+}
+
+/// The lean_ffi backend stops at the first policy failing to validate and only reports the kind of
+/// error, so each policy is validated on its own (using `validate_policyset`) to find all failing
+/// policies. Falls back on `error` without a policy id if no single policy fails to validate.
+/// The time spent validating each policy is recorded in `timings`, if any.
+fn policy_errors(
+    policyset: &PolicySet,
+    error: String,
+    validate_policyset: impl Fn(&PolicySet) -> Result<TimedResult<ValidationResponse>, FfiError>,
+    mut timings: Option<&mut Timings>,
+) -> Result<Vec<ValidationErrorSer>, ExecError> {
+    let mut errors = Vec::new();
+    for policy in policyset.policies().sorted_by_key(|p| p.id().to_string()) {
+        let singleton = singleton_policyset(policyset, policy)?;
+        let (response, duration) = split_timed(validate_policyset(&singleton)?);
+        if let Some(timings) = &mut timings {
+            timings.push(TimedStep::Policy(policy.id().clone()), duration);
+        }
+        if let ValidationResponse::Error(kind) = response {
+            errors.push(ValidationErrorSer {
+                policy_id: Some(policy.id().to_string()),
+                kind,
+                message: None,
+            });
+        }
+    }
+    if errors.is_empty() {
+        errors.push(ValidationErrorSer {
+            policy_id: None,
+            kind: error,
+            message: None,
+        });
+    }
+    Ok(errors)
+}
+
+/// The result of validating a `PolicySet`, attributing any errors to policies using `validate_policyset`
+/// (and recording the time spent doing so in `timings`, if any)
+pub(crate) fn policyset_validation_result(
+    policyset: &PolicySet,
+    response: ValidationResponse,
+    validate_policyset: impl Fn(&PolicySet) -> Result<TimedResult<ValidationResponse>, FfiError>,
+    timings: Option<&mut Timings>,
+) -> Result<ValidationResultSer, ExecError> {
+    match response {
+        ValidationResponse::Ok(()) => Ok(ValidationResultSer {
+            valid: true,
+            errors: Vec::new(),
+        }),
+        ValidationResponse::Error(s) => Ok(ValidationResultSer {
+            valid: false,
+            errors: policy_errors(policyset, s, validate_policyset, timings)?,
+        }),
+    }
+}
+
+/// The result of validating a `Request` or `Entities`. The lean_ffi backend reports these errors
+/// as type errors with a message.
+pub(crate) fn type_error_result(response: ValidationResponse) -> ValidationResultSer {
+    let errors = match response {
+        ValidationResponse::Ok(()) => Vec::new(),
+        ValidationResponse::Error(s) => vec![ValidationErrorSer {
+            policy_id: None,
+            kind: "typeError".to_string(),
+            message: Some(s),
+        }],
+    };
+    ValidationResultSer {
+        valid: errors.is_empty(),
+        errors,
+    }
+}
+
+/// The time spent in Lean by a validate command, if it is run with `--timings`
+fn validation_timings(duration: Duration, timings: bool) -> Option<Timings> {
+    timings.then(|| {
+        let mut validation_timings = Timings::default();
+        validation_timings.push(TimedStep::Validation, duration);
+        validation_timings
+    })
+}
+
+/// Print the outcome of a validate command, followed by the `timings` if any
+fn print_validation(message: String, timings: Option<&Timings>) {
+    println!("{message}");
+    if let Some(timings) = timings {
+        println!();
+        timings.print_table();
+    }
+}
 
 /// Validate (using the lean_ffi backend) that the input `PolicySet` matches the provided
 /// `Schema` for the given `ValidationMode` (Strict or Permissive)
@@ -23,63 +153,104 @@ pub fn validate(
     policyset: &PolicySet,
     schema: &Schema,
     mode: &ValidationMode,
+    json_output: bool,
+    timings: bool,
 ) -> Result<(), ExecError> {
     let lean_context = CedarLeanFfi::new();
-    match lean_context.validate(policyset, schema, mode)? {
-        ValidationResponse::Ok(()) => {
-            println!("Policyset successfully validated");
-            Ok(())
-        }
-        ValidationResponse::Error(s) => {
-            println!("Policyset failed to validate: {s}");
-            Ok(())
-        }
+    let (response, duration) = split_timed(lean_context.validate_timed(policyset, schema, mode)?);
+    let mut timings = validation_timings(duration, timings);
+    if json_output {
+        policyset_validation_result(
+            policyset,
+            response,
+            |pset| lean_context.validate_timed(pset, schema, mode),
+            timings.as_mut(),
+        )?
+        .print(timings.as_ref());
+        return Ok(());
     }
+    let message = match response {
+        ValidationResponse::Ok(()) => "Policyset successfully validated".to_string(),
+        ValidationResponse::Error(s) => format!("Policyset failed to validate: {s}"),
+    };
+    print_validation(message, timings.as_ref());
+    Ok(())
 }
 
 /// Validates (using the lean_ffi backend) that the input `PolicySet` matches the provided
 /// `Schema` at level `level`. Level 0 means no entity or context record fields are accessed.
 /// Level `i` means that record fields are accessed upto depth `i`.
-pub fn level_validate(policyset: &PolicySet, schema: &Schema, level: i32) -> Result<(), ExecError> {
+pub fn level_validate(
+    policyset: &PolicySet,
+    schema: &Schema,
+    level: i32,
+    json_output: bool,
+    timings: bool,
+) -> Result<(), ExecError> {
     let lean_context = CedarLeanFfi::new();
-    match lean_context.level_validate(policyset, schema, level)? {
-        ValidationResponse::Ok(()) => {
-            println!("Policyset successfully validated at level {level}");
-            Ok(())
-        }
+    let (response, duration) =
+        split_timed(lean_context.level_validate_timed(policyset, schema, level)?);
+    let mut timings = validation_timings(duration, timings);
+    if json_output {
+        policyset_validation_result(
+            policyset,
+            response,
+            |pset| lean_context.level_validate_timed(pset, schema, level),
+            timings.as_mut(),
+        )?
+        .print(timings.as_ref());
+        return Ok(());
+    }
+    let message = match response {
+        ValidationResponse::Ok(()) => format!("Policyset successfully validated at level {level}"),
         ValidationResponse::Error(s) => {
-            println!("Policyset failed to validate at level {level}: {s}");
-            Ok(())
+            format!("Policyset failed to validate at level {level}: {s}")
         }
-    }
+    };
+    print_validation(message, timings.as_ref());
+    Ok(())
 }
 
 /// Validates (using the lean_ffi backend) that the input `Entities` matches the provided `Schema`
-pub fn validate_entities(schema: &Schema, entities: &Entities) -> Result<(), ExecError> {
+pub fn validate_entities(
+    schema: &Schema,
+    entities: &Entities,
+    json_output: bool,
+    timings: bool,
+) -> Result<(), ExecError> {
     let lean_context = CedarLeanFfi::new();
-    match lean_context.validate_entities(schema, entities)? {
-        ValidationResponse::Ok(()) => {
-            println!("Entities successfully validated");
-            Ok(())
-        }
-        ValidationResponse::Error(s) => {
-            println!("Entities failed to validate: {s}");
-            Ok(())
-        }
+    let (response, duration) = split_timed(lean_context.validate_entities_timed(schema, entities)?);
+    let timings = validation_timings(duration, timings);
+    if json_output {
+        type_error_result(response).print(timings.as_ref());
+        return Ok(());
     }
+    let message = match response {
+        ValidationResponse::Ok(()) => "Entities successfully validated".to_string(),
+        ValidationResponse::Error(s) => format!("Entities failed to validate: {s}"),
+    };
+    print_validation(message, timings.as_ref());
+    Ok(())
 }
 
 /// Validates (using the lean_ffi backend) that the input `Request` matches the provided `Schema`
-pub fn validate_request(schema: &Schema, request: &Request) -> Result<(), ExecError> {
+pub fn validate_request(
+    schema: &Schema,
+    request: &Request,
+    json_output: bool,
+    timings: bool,
+) -> Result<(), ExecError> {
     let lean_context = CedarLeanFfi::new();
-    match lean_context.validate_request(schema, request)? {
-        ValidationResponse::Ok(()) => {
-            println!("Request successfully validated");
-            Ok(())
-        }
-        ValidationResponse::Error(s) => {
-            println!("Request failed to validate: {s}");
-            Ok(())
-        }
+    let (response, duration) = split_timed(lean_context.validate_request_timed(schema, request)?);
+    let timings = validation_timings(duration, timings);
+    if json_output {
+        type_error_result(response).print(timings.as_ref());
+        return Ok(());
     }
+    let message = match response {
+        ValidationResponse::Ok(()) => "Request successfully validated".to_string(),
+        ValidationResponse::Error(s) => format!("Request failed to validate: {s}"),
+    };
+    print_validation(message, timings.as_ref());
+    Ok(())
 }
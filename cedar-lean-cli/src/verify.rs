@@ -0,0 +1,485 @@
+/*
+ * Copyright Cedar Contributors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use crate::analysis::ExampleEnv;
+use crate::err::{ContentType, ExecError};
+use crate::symcc::{self, CheckOptions, CheckResult};
+use crate::util::{self, OpenRequestEnv, ReqEnv, SchemaCache};
+use cedar_lean_ffi::CedarLeanFfi;
+use cedar_policy::{Policy, PolicySet, RequestEnv};
+use itertools::Itertools;
+use prettytable::{Attr, Cell, Row, Table};
+use serde::Deserialize;
+use std::fmt::Write;
+use std::fs::read_to_string;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// A suite of named `symcc` checks, read from a TOML or JSON file
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Suite {
+    /// The schema of every check that does not name its own
+    schema: Option<PathBuf>,
+    #[serde(rename = "check")]
+    checks: Vec<CheckSpec>,
+}
+
+/// A named check, with the files it runs on. Paths are relative to the suite file.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct CheckSpec {
+    name: String,
+    kind: CheckKind,
+    schema: Option<PathBuf>,
+    /// For `never-errors`, `always-matches` and `never-matches`, the Policy to check
+    policy: Option<PathBuf>,
+    /// For `always-allows`, `always-denies` and `property`, the PolicySet to check. For
+    /// `never-errors`, `always-matches` and `never-matches`, each of its policies is checked.
+    policyset: Option<PathBuf>,
+    /// For `equivalent`, `implies` and `disjoint`, the PolicySets to compare
+    pset1: Option<PathBuf>,
+    pset2: Option<PathBuf>,
+    /// For `property`, the Cedar boolean expression every allowed request must satisfy
+    property: Option<PathBuf>,
+    /// For `matches-equivalent`, `matches-implies` and `matches-disjoint`, the Policies to compare
+    policy1: Option<PathBuf>,
+    policy2: Option<PathBuf>,
+    policy_id1: Option<String>,
+    policy_id2: Option<String>,
+    principal_type: Option<String>,
+    action_name: Option<String>,
+    resource_type: Option<String>,
+    #[serde(default)]
+    expect: Expectation,
+    /// Whether to report a counterexample for each request environment where the check does not hold
+    #[serde(default)]
+    cex: bool,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum CheckKind {
+    NeverErrors,
+    AlwaysMatches,
+    NeverMatches,
+    AlwaysAllows,
+    AlwaysDenies,
+    Equivalent,
+    Implies,
+    Disjoint,
+    Property,
+    MatchesEquivalent,
+    MatchesImplies,
+    MatchesDisjoint,
+}
+
+impl std::fmt::Display for CheckKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match self {
+            Self::NeverErrors => "never-errors",
+            Self::AlwaysMatches => "always-matches",
+            Self::NeverMatches => "never-matches",
+            Self::AlwaysAllows => "always-allows",
+            Self::AlwaysDenies => "always-denies",
+            Self::Equivalent => "equivalent",
+            Self::Implies => "implies",
+            Self::Disjoint => "disjoint",
+            Self::Property => "property",
+            Self::MatchesEquivalent => "matches-equivalent",
+            Self::MatchesImplies => "matches-implies",
+            Self::MatchesDisjoint => "matches-disjoint",
+        };
+        write!(f, "{kind}")
+    }
+}
+
+/// Whether a check is expected to hold for every request environment, or to be violated for some
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum Expectation {
+    #[default]
+    Holds,
+    Fails,
+}
+
+enum Outcome {
+    Passed,
+    /// The check did not meet its expectation
+    Failed(String),
+    /// The check could not be run
+    Error(String),
+}
+
+/// The outcome of a check (or, for checks run on each policy of a PolicySet, of one policy)
+struct TestCase {
+    name: String,
+    kind: CheckKind,
+    outcome: Outcome,
+    time: Duration,
+}
+
+/// Run every check of the suite in `suite_file` with a single `CedarLeanFfi`, print a summary,
+/// and write a JUnit XML report to `junit_file` (if provided). Fails if any check did not pass.
+pub fn verify(suite_file: &PathBuf, junit_file: Option<&PathBuf>) -> Result<(), ExecError> {
+    let suite = parse_suite(suite_file)?;
+    let base_dir = suite_file.parent().unwrap_or(Path::new(""));
+    let lean_ffi = CedarLeanFfi::new();
+    let mut schemas = SchemaCache::default();
+
+    let mut test_cases = Vec::new();
+    for spec in suite.checks.iter() {
+        let schema_file = spec.schema.as_ref().or(suite.schema.as_ref());
+        let start = Instant::now();
+        match run_check(&lean_ffi, &mut schemas, base_dir, schema_file, spec) {
+            Ok(results) => test_cases.extend(results),
+            Err(err) => test_cases.push(TestCase {
+                name: spec.name.clone(),
+                kind: spec.kind,
+                outcome: Outcome::Error(err.to_string()),
+                time: start.elapsed(),
+            }),
+        }
+    }
+
+    print_summary(&test_cases);
+    if let Some(junit_file) = junit_file {
+        let report = junit_report(&suite_file.display().to_string(), &test_cases);
+        std::fs::write(junit_file, report).map_err(|err| ExecError::FileWriteError {
+            content_type: ContentType::JUnitReport,
+            file_name: junit_file.clone(),
+            error: Box::new(err),
+        })?;
+    }
+
+    let failed = test_cases
+        .iter()
+        .filter(|test_case| !matches!(test_case.outcome, Outcome::Passed))
+        .count();
+    if failed > 0 {
+        return Err(ExecError::VerificationFailed {
+            failed,
+            total: test_cases.len(),
+        });
+    }
+    Ok(())
+}
+
+/// Parse a suite file, in JSON if its name ends in .json and in TOML otherwise
+fn parse_suite(fname: &PathBuf) -> Result<Suite, ExecError> {
+    let suite_text = read_to_string(fname).map_err(|err| ExecError::FileReadError {
+        content_type: ContentType::VerificationSuite,
+        file_name: fname.to_path_buf(),
+        error: Box::new(err),
+    })?;
+    let parse_error = |error: Box<dyn std::error::Error + Send + Sync>| ExecError::ParseError {
+        content_type: ContentType::VerificationSuite,
+        file_name: fname.to_path_buf(),
+        error,
+    };
+    if fname.extension().is_some_and(|ext| ext == "json") {
+        serde_json::from_str(&suite_text).map_err(|err| parse_error(Box::new(err)))
+    } else {
+        toml::from_str(&suite_text).map_err(|err| parse_error(Box::new(err)))
+    }
+}
+
+impl CheckSpec {
+    /// The path of the file in `field`, which the check requires
+    fn required_path(
+        &self,
+        base_dir: &Path,
+        field: Option<&PathBuf>,
+        name: &'static str,
+    ) -> Result<PathBuf, ExecError> {
+        match field {
+            Some(path) => Ok(base_dir.join(path)),
+            None => Err(ExecError::SuiteCheckMissingField {
+                check: self.name.clone(),
+                kind: self.kind.to_string(),
+                field: name,
+            }),
+        }
+    }
+
+    /// The policies to run a per-policy check on, each with the name of its test case
+    fn policies(&self, base_dir: &Path) -> Result<Vec<(String, Policy)>, ExecError> {
+        match (&self.policy, &self.policyset) {
+            (Some(policy_file), _) => Ok(vec![(
+                self.name.clone(),
+                util::parse_policy(&base_dir.join(policy_file))?,
+            )]),
+            (None, Some(policyset_file)) => {
+                Ok(util::parse_policyset(&base_dir.join(policyset_file))?
+                    .policies()
+                    .sorted_by_key(|policy| policy.id().to_string())
+                    .map(|policy| (format!("{} [{}]", self.name, policy.id()), policy.clone()))
+                    .collect())
+            }
+            (None, None) => Err(ExecError::SuiteCheckMissingField {
+                check: self.name.clone(),
+                kind: self.kind.to_string(),
+                field: "policy` or `policyset",
+            }),
+        }
+    }
+}
+
+/// Run the check described by `spec`, returning a test case per policy for checks run on each
+/// policy of a PolicySet, and a single test case otherwise
+fn run_check(
+    lean_ffi: &CedarLeanFfi,
+    schemas: &mut SchemaCache,
+    base_dir: &Path,
+    schema_file: Option<&PathBuf>,
+    spec: &CheckSpec,
+) -> Result<Vec<TestCase>, ExecError> {
+    let schema = schemas.get(
+        lean_ffi,
+        &spec.required_path(base_dir, schema_file, "schema")?,
+    )?;
+    let req_envs = OpenRequestEnv::new(
+        spec.principal_type.clone(),
+        spec.action_name.clone(),
+        spec.resource_type.clone(),
+    )?
+    .to_request_envs(&schema.schema)?;
+    let schema = schema.lean_schema.clone();
+    let opts = CheckOptions {
+        cex: spec.cex,
+        max_cex: NonZeroUsize::MIN,
+        minimize: None,
+        json_output: false,
+        export: None,
+        timings: false,
+    };
+    let policyset = |field: Option<&PathBuf>, name| -> Result<PolicySet, ExecError> {
+        util::parse_policyset(&spec.required_path(base_dir, field, name)?)
+    };
+    let policy =
+        |field: Option<&PathBuf>, name, policy_id: Option<&str>| -> Result<Policy, ExecError> {
+            util::parse_policy_with_id(&spec.required_path(base_dir, field, name)?, policy_id)
+        };
+    let single = |check: &dyn Fn() -> Result<Vec<CheckResult>, ExecError>| {
+        test_case(spec.name.clone(), spec, &req_envs, check).map(|test_case| vec![test_case])
+    };
+
+    match spec.kind {
+        CheckKind::NeverErrors | CheckKind::AlwaysMatches | CheckKind::NeverMatches => {
+            let check = match spec.kind {
+                CheckKind::NeverErrors => symcc::check_never_errors,
+                CheckKind::AlwaysMatches => symcc::check_always_matches,
+                _ => symcc::check_never_matches,
+            };
+            spec.policies(base_dir)?
+                .into_iter()
+                .map(|(name, policy)| {
+                    test_case(name, spec, &req_envs, || {
+                        check(lean_ffi, &policy, schema.clone(), &req_envs, &opts)
+                    })
+                })
+                .collect()
+        }
+        CheckKind::AlwaysAllows => {
+            let pset = policyset(spec.policyset.as_ref(), "policyset")?;
+            single(&|| {
+                symcc::check_always_allows(lean_ffi, &pset, schema.clone(), &req_envs, &opts)
+            })
+        }
+        CheckKind::AlwaysDenies => {
+            let pset = policyset(spec.policyset.as_ref(), "policyset")?;
+            single(&|| {
+                symcc::check_always_denies(lean_ffi, &pset, schema.clone(), &req_envs, &opts)
+            })
+        }
+        CheckKind::Equivalent | CheckKind::Implies | CheckKind::Disjoint => {
+            let check = match spec.kind {
+                CheckKind::Equivalent => symcc::check_equivalent,
+                CheckKind::Implies => symcc::check_implies,
+                _ => symcc::check_disjoint,
+            };
+            let pset1 = policyset(spec.pset1.as_ref(), "pset1")?;
+            let pset2 = policyset(spec.pset2.as_ref(), "pset2")?;
+            single(&|| check(lean_ffi, &pset1, &pset2, schema.clone(), &req_envs, &opts))
+        }
+        CheckKind::Property => {
+            let pset = policyset(spec.policyset.as_ref(), "policyset")?;
+            let property = util::parse_expression(&spec.required_path(
+                base_dir,
+                spec.property.as_ref(),
+                "property",
+            )?)?;
+            single(&|| {
+                symcc::check_property(lean_ffi, &pset, &property, schema.clone(), &req_envs, &opts)
+            })
+        }
+        CheckKind::MatchesEquivalent | CheckKind::MatchesImplies | CheckKind::MatchesDisjoint => {
+            let check = match spec.kind {
+                CheckKind::MatchesEquivalent => symcc::check_matches_equivalent,
+                CheckKind::MatchesImplies => symcc::check_matches_implies,
+                _ => symcc::check_matches_disjoint,
+            };
+            let policy1 = policy(spec.policy1.as_ref(), "policy1", spec.policy_id1.as_deref())?;
+            let policy2 = policy(spec.policy2.as_ref(), "policy2", spec.policy_id2.as_deref())?;
+            single(&|| {
+                check(
+                    lean_ffi,
+                    &policy1,
+                    &policy2,
+                    schema.clone(),
+                    &req_envs,
+                    &opts,
+                )
+            })
+        }
+    }
+}
+
+/// Run `check`, and compare its results for each of the `req_envs` to the expectation of `spec`
+fn test_case(
+    name: String,
+    spec: &CheckSpec,
+    req_envs: &[RequestEnv],
+    check: impl FnOnce() -> Result<Vec<CheckResult>, ExecError>,
+) -> Result<TestCase, ExecError> {
+    let start = Instant::now();
+    let results = check()?;
+    let time = start.elapsed();
+    let mut violations = String::new();
+    let mut unknowns = String::new();
+    for (req_env, result) in req_envs.iter().zip(results.iter()) {
+        match result.holds {
+            Some(true) => continue,
+            Some(false) => (),
+            None => {
+                writeln!(
+                    unknowns,
+                    "The solver could not decide RequestEnv {}",
+                    ReqEnv::Env(req_env.clone())
+                )
+                .unwrap();
+                continue;
+            }
+        }
+        writeln!(
+            violations,
+            "Does not hold for RequestEnv {}",
+            ReqEnv::Env(req_env.clone())
+        )
+        .unwrap();
+        for cex in result.cexs.iter() {
+            write!(violations, "{}", ExampleEnv(cex.clone())).unwrap();
+        }
+    }
+    let outcome = match (spec.expect, violations.is_empty()) {
+        (Expectation::Fails, false) => Outcome::Passed,
+        (Expectation::Holds, false) => Outcome::Failed(violations),
+        // Without a violation, the expectation is only met if every RequestEnv was decided
+        (_, true) if !unknowns.is_empty() => Outcome::Error(unknowns),
+        (Expectation::Holds, true) => Outcome::Passed,
+        (Expectation::Fails, true) => Outcome::Failed(String::from(
+            "Holds for every RequestEnv, but was expected to fail",
+        )),
+    };
+    Ok(TestCase {
+        name,
+        kind: spec.kind,
+        outcome,
+        time,
+    })
+}
+
+fn print_summary(test_cases: &[TestCase]) {
+    let mut table = Table::new();
+    // Print a nice header
+    table.add_row(Row::new(vec![
+        Cell::new("Check").with_style(Attr::Bold),
+        Cell::new("Kind").with_style(Attr::Bold),
+        Cell::new("Result").with_style(Attr::Bold),
+        Cell::new("Details").with_style(Attr::Bold),
+    ]));
+    for test_case in test_cases.iter() {
+        let (result, details) = match &test_case.outcome {
+            Outcome::Passed => ("PASS", ""),
+            Outcome::Failed(details) => ("FAIL", details.trim_end()),
+            Outcome::Error(details) => ("ERROR", details.trim_end()),
+        };
+        table.add_row(Row::new(vec![
+            Cell::new(&test_case.name),
+            Cell::new(&test_case.kind.to_string()),
+            Cell::new(result),
+            Cell::new(details),
+        ]));
+    }
+    table.printstd();
+    let passed = test_cases
+        .iter()
+        .filter(|test_case| matches!(test_case.outcome, Outcome::Passed))
+        .count();
+    println!("{passed} of {} checks passed", test_cases.len());
+}
+
+/// A JUnit XML report of the `test_cases`, as a single test suite named `suite_name`
+fn junit_report(suite_name: &str, test_cases: &[TestCase]) -> String {
+    let count = |f: fn(&Outcome) -> bool| test_cases.iter().filter(|tc| f(&tc.outcome)).count();
+    let total_time: Duration = test_cases.iter().map(|test_case| test_case.time).sum();
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites>\n");
+    writeln!(
+        xml,
+        "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{:.3}\">",
+        xml_escape(suite_name),
+        test_cases.len(),
+        count(|outcome| matches!(outcome, Outcome::Failed(_))),
+        count(|outcome| matches!(outcome, Outcome::Error(_))),
+        total_time.as_secs_f64()
+    )
+    .unwrap();
+    for test_case in test_cases.iter() {
+        let attrs = format!(
+            "name=\"{}\" classname=\"{}\" time=\"{:.3}\"",
+            xml_escape(&test_case.name),
+            test_case.kind,
+            test_case.time.as_secs_f64()
+        );
+        match &test_case.outcome {
+            Outcome::Passed => writeln!(xml, "    <testcase {attrs}/>").unwrap(),
+            Outcome::Failed(details) | Outcome::Error(details) => {
+                let tag = match test_case.outcome {
+                    Outcome::Failed(_) => "failure",
+                    _ => "error",
+                };
+                let message = details.lines().next().unwrap_or_default();
+                writeln!(
+                    xml,
+                    "    <testcase {attrs}>\n      <{tag} message=\"{}\">{}</{tag}>\n    </testcase>",
+                    xml_escape(message),
+                    xml_escape(details)
+                )
+                .unwrap()
+            }
+        }
+    }
+    xml.push_str("  </testsuite>\n</testsuites>\n");
+    xml
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
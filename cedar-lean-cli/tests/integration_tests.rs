@@ -1,4 +1,6 @@
 use assert_cmd::{Command, cargo};
+use cedar_testing::cedar_test_impl::RustEngine;
+use cedar_testing::integration_testing::perform_integration_test_from_json_custom;
 use std::sync::LazyLock;
 
 #[track_caller]
@@ -35,6 +37,14 @@ static SETTINGS: LazyLock<insta::Settings> = LazyLock::new(|| {
     settings
 });
 
+static RESIDUAL_SETTINGS: LazyLock<insta::Settings> = LazyLock::new(|| {
+    let mut settings = SETTINGS.clone();
+    // the residual conditions are simplified by Lean and printed by `pst`, so only which policies
+    // have residuals (and their effects) is checked
+    settings.add_filter(r"when \{ .* \};", "when { <residual> };");
+    settings
+});
+
 /***************************************** Tests for Analysis *****************************************/
 #[test]
 fn test_analyze_policies_tabular_view_box_p1() {
@@ -126,6 +136,230 @@ fn test_analyze_policies_tabular_online_docs() {
     });
 }
 
+#[test]
+fn test_analyze_policies_tabular_online_docs_jobs() {
+    // the parallel analysis must produce exactly the sequential output
+    SETTINGS.bind(|| {
+        insta::assert_snapshot!(
+            "analyze_policies_tabular_online_docs",
+            cmd_output(
+                &mut cargo::cargo_bin_cmd!()
+                    .current_dir("examples/analyze/online_docs")
+                    .arg("analyze")
+                    .arg("policies")
+                    .arg("policies.cedar")
+                    .arg("policies.cedarschema")
+                    .arg("--jobs")
+                    .arg("4"),
+                false,
+            )
+        );
+    });
+}
+
+#[test]
+fn test_analyze_minimize_tabular_view_box_p5() {
+    let output_file =
+        std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("minimize_view_box_p5.cedar");
+    let mut settings = SETTINGS.clone();
+    // the output file is outside the examples directory, so its path is redacted
+    settings.add_filter(r"\S*minimize_view_box_p5\.cedar", "<output-file>");
+    settings.bind(|| {
+        insta::assert_snapshot!(cmd_output(
+            &mut cargo::cargo_bin_cmd!()
+                .current_dir("examples/analyze/view_box")
+                .arg("analyze")
+                .arg("minimize")
+                .arg("policies5.cedar")
+                .arg("policies.cedarschema")
+                .arg("--output-file")
+                .arg(&output_file),
+            false,
+        ));
+        // every remaining policy is written with its id
+        insta::assert_snapshot!(
+            "analyze_minimize_tabular_view_box_p5_output",
+            std::fs::read_to_string(&output_file).unwrap()
+        );
+    });
+}
+
+#[test]
+fn test_analyze_policies_tabular_view_box_templates() {
+    SETTINGS.bind(|| {
+        insta::assert_snapshot!(cmd_output(
+            &mut cargo::cargo_bin_cmd!()
+                .current_dir("examples/analyze/view_box")
+                .arg("analyze")
+                .arg("policies")
+                .arg("templates.cedar")
+                .arg("policies.cedarschema")
+                .arg("--links")
+                .arg("links.json"),
+            false,
+        ));
+    });
+}
+
+#[test]
+fn test_analyze_policies_json_view_box_templates() {
+    let mut settings = SETTINGS.clone();
+    // the policy text is formatted by cedar-policy
+    settings.add_filter(r#""policy_str": ".*""#, r#""policy_str": "<policy>""#);
+    settings.bind(|| {
+        insta::assert_snapshot!(cmd_output(
+            &mut cargo::cargo_bin_cmd!()
+                .current_dir("examples/analyze/view_box")
+                .arg("analyze")
+                .arg("policies")
+                .arg("templates.cedar")
+                .arg("policies.cedarschema")
+                .arg("--links")
+                .arg("links.json")
+                .arg("--json-output"),
+            false,
+        ));
+    });
+}
+
+#[test]
+fn test_analyze_policies_sarif_view_box() {
+    // not bound to `SETTINGS`, which would redact the line and column numbers of the regions
+    insta::assert_snapshot!(cmd_output(
+        &mut cargo::cargo_bin_cmd!()
+            .current_dir("examples/analyze/view_box")
+            .arg("analyze")
+            .arg("policies")
+            .arg("sarif.cedar")
+            .arg("policies.cedarschema")
+            .arg("--links")
+            .arg("links.json")
+            .arg("--sarif"),
+        false,
+    ));
+}
+
+#[test]
+fn test_analyze_policies_check_errors_view_box() {
+    let output = cmd_output(
+        &mut cargo::cargo_bin_cmd!()
+            .current_dir("examples/analyze/view_box")
+            .arg("analyze")
+            .arg("policies")
+            .arg("errors.cedar")
+            .arg("policies.cedarschema")
+            .arg("--check-errors")
+            .arg("--json-output"),
+        false,
+    );
+    let results: serde_json::Value = serde_json::from_str(&output).unwrap();
+    // the example environments are left out, as the solver may pick any in which the policy errors,
+    // except that `overflows_if_empty` only errors when `n1` is empty
+    let mut findings = Vec::new();
+    for sig_findings in results["per_sig_findings"].as_array().unwrap() {
+        let req_env = &sig_findings["req_env"];
+        for class in ["always_errors", "may_error"] {
+            for finding in sig_findings[class].as_array().into_iter().flatten() {
+                let pid = finding["policy"]["policy_id"].as_str().unwrap();
+                if pid == "overflows_if_empty" {
+                    assert_eq!(
+                        finding["example"]["request"]["context"],
+                        serde_json::json!([["n1", { "prim": { "p": { "string": "" } } }]])
+                    );
+                }
+                findings.push(format!(
+                    "{}, {}, {}: {class}: {pid}",
+                    req_env["principal_type"].as_str().unwrap(),
+                    req_env["action_uid"].as_str().unwrap(),
+                    req_env["resource_type"].as_str().unwrap(),
+                ));
+            }
+        }
+    }
+    insta::assert_snapshot!(findings.join("\n"));
+}
+
+#[test]
+fn test_analyze_policies_unknown_solver_view_box() {
+    let output = cargo::cargo_bin_cmd!()
+        .current_dir("examples/analyze/view_box")
+        .arg("analyze")
+        .arg("policies")
+        .arg("policies1.cedar")
+        .arg("policies.cedarschema")
+        .arg("--json-output")
+        .arg("--solver")
+        .arg(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/unknown_solver.sh"
+        ))
+        .output()
+        .expect("Failed to execute command");
+    let stderr = String::from_utf8(output.stderr).expect("Failed to convert stderr to string");
+    assert!(output.status.success(), "unexpected stderr:\n{stderr}");
+    assert!(
+        stderr.contains("Warning: the solver could not decide"),
+        "unexpected stderr:\n{stderr}"
+    );
+    let results: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    // no query is decided, so nothing is known about the policies
+    assert_eq!(results["vacuous_result"], "Unknown");
+    let statuses: Vec<&str> = results["vacuous_policies"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|finding| finding["status"].as_str().unwrap())
+        .collect();
+    assert_eq!(statuses, ["Unknown", "Unknown"]);
+}
+
+#[test]
+fn test_analyze_minimize_tabular_view_box_templates() {
+    let output_file =
+        std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("minimize_view_box_templates.json");
+    let mut settings = SETTINGS.clone();
+    // the output file is outside the examples directory, so its path is redacted
+    settings.add_filter(r"\S*minimize_view_box_templates\.json", "<output-file>");
+    settings.bind(|| {
+        insta::assert_snapshot!(cmd_output(
+            &mut cargo::cargo_bin_cmd!()
+                .current_dir("examples/analyze/view_box")
+                .arg("analyze")
+                .arg("minimize")
+                .arg("templates.cedar")
+                .arg("policies.cedarschema")
+                .arg("--links")
+                .arg("links.json")
+                .arg("--output-file")
+                .arg(&output_file),
+            false,
+        ));
+    });
+    // the template-linked policy keeps its id and link in the written PolicySet
+    assert_eq!(
+        cmd_output(
+            &mut cargo::cargo_bin_cmd!()
+                .current_dir("examples/analyze/view_box")
+                .arg("analyze")
+                .arg("policies")
+                .arg(&output_file)
+                .arg("policies.cedarschema"),
+            false,
+        ),
+        cmd_output(
+            &mut cargo::cargo_bin_cmd!()
+                .current_dir("examples/analyze/view_box")
+                .arg("analyze")
+                .arg("policies")
+                .arg("templates.cedar")
+                .arg("policies.cedarschema")
+                .arg("--links")
+                .arg("links.json"),
+            false,
+        )
+    );
+}
+
 #[test]
 fn test_analyze_compare_tabular_view_box_trivial1() {
     SETTINGS.bind(|| {
@@ -761,3 +995,677 @@ fn test_analyze_compare_tabular_demo_4_3() {
         ));
     });
 }
+
+#[test]
+fn test_analyze_compare_attribute_tabular_demo_2_1() {
+    SETTINGS.bind(|| {
+        insta::assert_snapshot!(cmd_output(
+            &mut cargo::cargo_bin_cmd!()
+                .current_dir("examples/analyze/file_share_demo")
+                .arg("analyze")
+                .arg("compare")
+                .arg("policies2.cedar")
+                .arg("policies1.cedar")
+                .arg("policies.cedarschema")
+                .arg("--action-name")
+                .arg("DeleteUser")
+                .arg("--attribute"),
+            false,
+        ));
+    });
+}
+
+#[test]
+fn test_analyze_compare_attribute_json_demo_2_1() {
+    let output = cmd_output(
+        &mut cargo::cargo_bin_cmd!()
+            .current_dir("examples/analyze/file_share_demo")
+            .arg("analyze")
+            .arg("compare")
+            .arg("policies2.cedar")
+            .arg("policies1.cedar")
+            .arg("policies.cedarschema")
+            .arg("--action-name")
+            .arg("DeleteUser")
+            .arg("--attribute")
+            .arg("--json-output"),
+        false,
+    );
+    let results: serde_json::Value = serde_json::from_str(&output).unwrap();
+    // the example in `results` is covered by the tabular test above
+    insta::assert_snapshot!(serde_json::to_string_pretty(&results["attributions"]).unwrap());
+}
+
+/**************************************** Tests for Evaluation ****************************************/
+#[test]
+fn test_evaluate_batched_authorize_view_box() {
+    SETTINGS.bind(|| {
+        insta::assert_snapshot!(cmd_output(
+            &mut cargo::cargo_bin_cmd!()
+                .current_dir("examples/evaluate/view_box")
+                .arg("evaluate")
+                .arg("batched-authorize")
+                .arg("policies.cedar")
+                .arg("policies.cedarschema")
+                .arg("--entity-store")
+                .arg("entities.jsonl")
+                .arg("--requests-file")
+                .arg("requests.jsonl"),
+            false,
+        ));
+    });
+}
+
+#[test]
+fn test_evaluate_batched_authorize_rejects_duplicate_entities() {
+    let output = cargo::cargo_bin_cmd!()
+        .current_dir("examples/evaluate/view_box")
+        .arg("evaluate")
+        .arg("batched-authorize")
+        .arg("policies.cedar")
+        .arg("policies.cedarschema")
+        .arg("--entity-store")
+        .arg("duplicate_entities.jsonl")
+        .arg("--requests-file")
+        .arg("requests.jsonl")
+        .output()
+        .expect("Failed to execute command");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).expect("Failed to convert stderr to string");
+    assert!(
+        stderr.contains(
+            r#"Entity Thing::"gear" is defined more than once in duplicate_entities.jsonl"#
+        ),
+        "unexpected stderr:\n{stderr}"
+    );
+}
+
+#[test]
+fn test_evaluate_bulk_authorize_view_box() {
+    SETTINGS.bind(|| {
+        insta::assert_snapshot!(cmd_output(
+            &mut cargo::cargo_bin_cmd!()
+                .current_dir("examples/evaluate/view_box")
+                .arg("evaluate")
+                .arg("bulk-authorize")
+                .arg("policies.cedar")
+                .arg("entities.json")
+                .arg("policies.cedarschema")
+                .arg("--requests-file")
+                .arg("requests.jsonl"),
+            false,
+        ));
+    });
+}
+
+#[test]
+fn test_evaluate_authorize_json_view_box() {
+    SETTINGS.bind(|| {
+        insta::assert_snapshot!(cmd_output(
+            &mut cargo::cargo_bin_cmd!()
+                .current_dir("examples/evaluate/view_box")
+                .arg("evaluate")
+                .arg("authorize")
+                .arg("policies.cedar")
+                .arg("entities.json")
+                .arg("policies.cedarschema")
+                .arg("--principal")
+                .arg(r#"User::"amelia""#)
+                .arg("--action")
+                .arg(r#"Action::"view""#)
+                .arg("--resource")
+                .arg(r#"Thing::"diary""#)
+                .arg("--context")
+                .arg(r#"{"n1": "b"}"#)
+                .arg("--json-output"),
+            false,
+        ));
+    });
+}
+
+#[test]
+fn test_evaluate_partial_authorize_decided_view_box() {
+    SETTINGS.bind(|| {
+        insta::assert_snapshot!(cmd_output(
+            &mut cargo::cargo_bin_cmd!()
+                .current_dir("examples/evaluate/view_box")
+                .arg("evaluate")
+                .arg("partial-authorize")
+                .arg("policies.cedar")
+                .arg("entities.json")
+                .arg("policies.cedarschema")
+                .arg("--principal")
+                .arg(r#"User::"amelia""#)
+                .arg("--action")
+                .arg(r#"Action::"view""#)
+                .arg("--resource")
+                .arg(r#"Thing::"gear""#),
+            false,
+        ));
+    });
+}
+
+#[test]
+fn test_evaluate_partial_authorize_residual_view_box() {
+    RESIDUAL_SETTINGS.bind(|| {
+        insta::assert_snapshot!(cmd_output(
+            &mut cargo::cargo_bin_cmd!()
+                .current_dir("examples/evaluate/view_box")
+                .arg("evaluate")
+                .arg("partial-authorize")
+                .arg("policies.cedar")
+                .arg("entities.json")
+                .arg("policies.cedarschema")
+                .arg("--principal-type")
+                .arg("User")
+                .arg("--action")
+                .arg(r#"Action::"view""#)
+                .arg("--resource")
+                .arg(r#"Thing::"diary""#),
+            false,
+        ));
+    });
+}
+
+#[test]
+fn test_evaluate_query_resources_decided_view_box() {
+    SETTINGS.bind(|| {
+        insta::assert_snapshot!(cmd_output(
+            &mut cargo::cargo_bin_cmd!()
+                .current_dir("examples/evaluate/view_box")
+                .arg("evaluate")
+                .arg("query-resources")
+                .arg("policies.cedar")
+                .arg("entities.json")
+                .arg("policies.cedarschema")
+                .arg("--principal")
+                .arg(r#"User::"bob""#)
+                .arg("--action")
+                .arg(r#"Action::"view""#)
+                .arg("--resource-type")
+                .arg("Box")
+                .arg("--context")
+                .arg(r#"{"n1": "a"}"#),
+            false,
+        ));
+    });
+}
+
+#[test]
+fn test_evaluate_query_principals_residual_view_box() {
+    RESIDUAL_SETTINGS.bind(|| {
+        insta::assert_snapshot!(cmd_output(
+            &mut cargo::cargo_bin_cmd!()
+                .current_dir("examples/evaluate/view_box")
+                .arg("evaluate")
+                .arg("query-principals")
+                .arg("policies.cedar")
+                .arg("entities.json")
+                .arg("policies.cedarschema")
+                .arg("--principal-type")
+                .arg("User")
+                .arg("--action")
+                .arg(r#"Action::"view""#)
+                .arg("--resource")
+                .arg(r#"Thing::"gear""#)
+                .arg("--context")
+                .arg(r#"{"n1": "a"}"#),
+            false,
+        ));
+    });
+}
+
+#[test]
+fn test_evaluate_query_actions_decided_view_box() {
+    SETTINGS.bind(|| {
+        insta::assert_snapshot!(cmd_output(
+            &mut cargo::cargo_bin_cmd!()
+                .current_dir("examples/evaluate/view_box")
+                .arg("evaluate")
+                .arg("query-actions")
+                .arg("policies.cedar")
+                .arg("entities.json")
+                .arg("policies.cedarschema")
+                .arg("--principal")
+                .arg(r#"User::"amelia""#)
+                .arg("--resource")
+                .arg(r#"Thing::"gear""#),
+            false,
+        ));
+    });
+}
+
+#[test]
+fn test_evaluate_query_actions_residual_view_box() {
+    RESIDUAL_SETTINGS.bind(|| {
+        insta::assert_snapshot!(cmd_output(
+            &mut cargo::cargo_bin_cmd!()
+                .current_dir("examples/evaluate/view_box")
+                .arg("evaluate")
+                .arg("query-actions")
+                .arg("policies.cedar")
+                .arg("partial_entities.json")
+                .arg("policies.cedarschema")
+                .arg("--principal")
+                .arg(r#"User::"amelia""#)
+                .arg("--resource")
+                .arg(r#"Thing::"diary""#),
+            false,
+        ));
+    });
+}
+
+#[test]
+fn test_evaluate_evaluate_view_box() {
+    SETTINGS.bind(|| {
+        insta::assert_snapshot!(cmd_output(
+            &mut cargo::cargo_bin_cmd!()
+                .current_dir("examples/evaluate/view_box")
+                .arg("evaluate")
+                .arg("evaluate")
+                .arg("description.cedar")
+                .arg("entities.json")
+                .arg("policies.cedarschema")
+                .arg("--principal")
+                .arg(r#"User::"amelia""#)
+                .arg("--action")
+                .arg(r#"Action::"view""#)
+                .arg("--resource")
+                .arg(r#"Thing::"gear""#)
+                .arg("--context")
+                .arg(r#"{"n1": "a"}"#),
+            false,
+        ));
+    });
+}
+
+#[test]
+fn test_evaluate_evaluate_json_view_box() {
+    SETTINGS.bind(|| {
+        insta::assert_snapshot!(cmd_output(
+            &mut cargo::cargo_bin_cmd!()
+                .current_dir("examples/evaluate/view_box")
+                .arg("evaluate")
+                .arg("evaluate")
+                .arg("description.cedar")
+                .arg("entities.json")
+                .arg("policies.cedarschema")
+                .arg("--principal")
+                .arg(r#"User::"amelia""#)
+                .arg("--action")
+                .arg(r#"Action::"view""#)
+                .arg("--resource")
+                .arg(r#"Thing::"gear""#)
+                .arg("--context")
+                .arg(r#"{"n1": "a"}"#)
+                .arg("--json-output"),
+            false,
+        ));
+    });
+}
+
+/**************************************** Tests for Validation ****************************************/
+#[test]
+fn test_validate_policy_set_json_view_box() {
+    SETTINGS.bind(|| {
+        insta::assert_snapshot!(cmd_output(
+            &mut cargo::cargo_bin_cmd!()
+                .current_dir("examples/analyze/view_box")
+                .arg("validate")
+                .arg("policy-set")
+                .arg("policies1.cedar")
+                .arg("policies.cedarschema")
+                .arg("--json-output"),
+            false,
+        ));
+    });
+}
+
+#[test]
+fn test_validate_policy_set_json_timings_view_box() {
+    let mut settings = insta::Settings::clone_current();
+    // the durations vary from run to run, but every policy validated on its own must be reported
+    settings.add_filter(r#""(duration|total)_ms": [\d.e+-]+"#, r#""${1}_ms": <ms>"#);
+    settings.bind(|| {
+        insta::assert_snapshot!(cmd_output(
+            &mut cargo::cargo_bin_cmd!()
+                .current_dir("examples/analyze/view_box")
+                .arg("validate")
+                .arg("policy-set")
+                .arg("invalid.cedar")
+                .arg("policies.cedarschema")
+                .arg("--json-output")
+                .arg("--timings"),
+            false,
+        ));
+    });
+}
+
+/******************************************* Tests for Diff *******************************************/
+#[test]
+fn test_diff_authorize_view_box() {
+    SETTINGS.bind(|| {
+        insta::assert_snapshot!(cmd_output(
+            &mut cargo::cargo_bin_cmd!()
+                .current_dir("examples/evaluate/view_box")
+                .arg("diff")
+                .arg("authorize")
+                .arg("policies.cedar")
+                .arg("entities.json")
+                .arg("policies.cedarschema")
+                .arg("--principal")
+                .arg(r#"User::"amelia""#)
+                .arg("--action")
+                .arg(r#"Action::"view""#)
+                .arg("--resource")
+                .arg(r#"Thing::"diary""#)
+                .arg("--context")
+                .arg(r#"{"n1": "b"}"#),
+            false,
+        ));
+    });
+}
+
+#[test]
+fn test_diff_policy_set_view_box() {
+    SETTINGS.bind(|| {
+        insta::assert_snapshot!(cmd_output(
+            &mut cargo::cargo_bin_cmd!()
+                .current_dir("examples/analyze/view_box")
+                .arg("diff")
+                .arg("policy-set")
+                .arg("policies1.cedar")
+                .arg("policies.cedarschema"),
+            false,
+        ));
+    });
+}
+
+/****************************************** Tests for Serve *******************************************/
+#[test]
+fn test_serve_stdin_view_box() {
+    let requests = [
+        r#"{"jsonrpc": "2.0", "id": 1, "method": "validate/policy-set", "params": {"policyset-file": "policies1.cedar", "schema-file": "policies.cedarschema", "validation-mode": "strict"}}"#,
+        r#"{"jsonrpc": "2.0", "id": 2, "method": "validate/policy-set", "params": {"policyset-file": "policies1.cedar", "schema-file": "policies.cedarschema", "validation-mode": "permissive"}}"#,
+        r#"{"jsonrpc": "2.0", "method": "validate/policy-set", "params": {"policyset-file": "policies1.cedar", "schema-file": "policies.cedarschema"}}"#,
+        r#"{"jsonrpc": "2.0", "id": 3, "method": "validate/request", "params": {"schema-file": "policies.cedarschema", "principal": "User::\"amelia\"", "action": "Action::\"view\"", "resource": "Box::\"widgets\"", "context": {"n1": "a"}}}"#,
+        r#"{"jsonrpc": "2.0", "id": 4, "method": "validate/everything", "params": {}}"#,
+    ];
+    SETTINGS.bind(|| {
+        insta::assert_snapshot!(cmd_output(
+            cargo::cargo_bin_cmd!()
+                .current_dir("examples/analyze/view_box")
+                .arg("serve")
+                .write_stdin(requests.join("\n")),
+            false,
+        ));
+    });
+}
+
+/****************************************** Tests for Verify ******************************************/
+#[test]
+fn test_verify_view_box() {
+    SETTINGS.bind(|| {
+        insta::assert_snapshot!(cmd_output(
+            &mut cargo::cargo_bin_cmd!()
+                .current_dir("examples/analyze/view_box")
+                .arg("verify")
+                .arg("suite.toml"),
+            false,
+        ));
+    });
+}
+
+#[test]
+fn test_verify_failing_view_box() {
+    let junit_file =
+        std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("verify_failing_view_box.xml");
+    let output = cargo::cargo_bin_cmd!()
+        .current_dir("examples/analyze/view_box")
+        .arg("verify")
+        .arg("failing_suite.toml")
+        .arg("--junit")
+        .arg(&junit_file)
+        .output()
+        .expect("Failed to execute command");
+    // a violated expectation (or a check that cannot run) makes the command fail
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8(output.stderr).expect("Failed to convert stderr to string");
+    assert!(
+        stderr.contains("2 of 3 checks failed"),
+        "unexpected stderr:\n{stderr}"
+    );
+    let mut settings = SETTINGS.clone();
+    // the time taken by the checks varies between runs
+    settings.add_filter(r#"time="\d+\.\d+""#, r#"time="<time>""#);
+    settings.bind(|| {
+        insta::assert_snapshot!(
+            String::from_utf8(output.stdout).expect("Failed to convert stdout to string")
+        );
+        insta::assert_snapshot!(
+            "verify_failing_view_box_junit",
+            std::fs::read_to_string(&junit_file).unwrap()
+        );
+    });
+}
+
+/****************************************** Tests for SymCC *******************************************/
+#[test]
+fn test_symcc_check_implies_cex_view_box() {
+    SETTINGS.bind(|| {
+        insta::assert_snapshot!(cmd_output(
+            &mut cargo::cargo_bin_cmd!()
+                .current_dir("examples/analyze/view_box")
+                .arg("symcc")
+                .arg("check-implies")
+                .arg("permit_all.cedar")
+                .arg("deny_all.cedar")
+                .arg("policies.cedarschema")
+                .arg("--cex"),
+            false,
+        ));
+    });
+}
+
+#[test]
+fn test_symcc_check_implies_cex_json_view_box() {
+    SETTINGS.bind(|| {
+        insta::assert_snapshot!(cmd_output(
+            &mut cargo::cargo_bin_cmd!()
+                .current_dir("examples/analyze/view_box")
+                .arg("symcc")
+                .arg("check-implies")
+                .arg("permit_all.cedar")
+                .arg("deny_all.cedar")
+                .arg("policies.cedarschema")
+                .arg("--cex")
+                .arg("--json-output"),
+            false,
+        ));
+    });
+}
+
+#[test]
+fn test_symcc_check_implies_max_cex_view_box() {
+    let output = cmd_output(
+        &mut cargo::cargo_bin_cmd!()
+            .current_dir("examples/analyze/view_box")
+            .arg("symcc")
+            .arg("check-implies")
+            .arg("widgets_viewers.cedar")
+            .arg("deny_all.cedar")
+            .arg("policies.cedarschema")
+            .arg("--resource-type")
+            .arg("Box")
+            .arg("--cex")
+            .arg("--max-cex")
+            .arg("3")
+            .arg("--json-output"),
+        false,
+    );
+    let results: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let euid = |euid: &serde_json::Value| {
+        format!("{}::{}", euid["ty"]["id"].as_str().unwrap(), euid["eid"])
+    };
+    // only two principal/resource pairs are allowed, so the enumeration stops after finding both,
+    // in whichever order the solver finds them
+    let mut cexs: Vec<String> = std::iter::once(&results[0]["counterexample"])
+        .chain(results[0]["additional_counterexamples"].as_array().unwrap())
+        .map(|cex| {
+            format!(
+                "principal: {}, resource: {}",
+                euid(&cex["request"]["principal"]),
+                euid(&cex["request"]["resource"])
+            )
+        })
+        .collect();
+    cexs.sort();
+    insta::assert_snapshot!(cexs.join("\n"));
+}
+
+#[test]
+fn test_symcc_check_always_matches_view_box() {
+    SETTINGS.bind(|| {
+        insta::assert_snapshot!(cmd_output(
+            &mut cargo::cargo_bin_cmd!()
+                .current_dir("examples/analyze/view_box")
+                .arg("symcc")
+                .arg("check-always-matches")
+                .arg("permit_all.cedar")
+                .arg("policies.cedarschema"),
+            false,
+        ));
+    });
+}
+
+#[test]
+fn test_symcc_check_never_matches_view_box() {
+    SETTINGS.bind(|| {
+        insta::assert_snapshot!(cmd_output(
+            &mut cargo::cargo_bin_cmd!()
+                .current_dir("examples/analyze/view_box")
+                .arg("symcc")
+                .arg("check-never-matches")
+                .arg("policies10.cedar")
+                .arg("policies.cedarschema"),
+            false,
+        ));
+    });
+}
+
+#[test]
+fn test_symcc_check_matches_equivalent_view_box() {
+    SETTINGS.bind(|| {
+        insta::assert_snapshot!(cmd_output(
+            &mut cargo::cargo_bin_cmd!()
+                .current_dir("examples/analyze/view_box")
+                .arg("symcc")
+                .arg("check-matches-equivalent")
+                .arg("permit_all.cedar")
+                .arg("policies10.cedar")
+                .arg("policies.cedarschema"),
+            false,
+        ));
+    });
+}
+
+#[test]
+fn test_symcc_check_matches_implies_view_box() {
+    SETTINGS.bind(|| {
+        insta::assert_snapshot!(cmd_output(
+            &mut cargo::cargo_bin_cmd!()
+                .current_dir("examples/analyze/view_box")
+                .arg("symcc")
+                .arg("check-matches-implies")
+                .arg("policies10.cedar")
+                .arg("permit_all.cedar")
+                .arg("policies.cedarschema"),
+            false,
+        ));
+    });
+}
+
+#[test]
+fn test_symcc_check_matches_disjoint_view_box() {
+    SETTINGS.bind(|| {
+        insta::assert_snapshot!(cmd_output(
+            &mut cargo::cargo_bin_cmd!()
+                .current_dir("examples/analyze/view_box")
+                .arg("symcc")
+                .arg("check-matches-disjoint")
+                .arg("policies9.cedar")
+                .arg("policies10.cedar")
+                .arg("policies.cedarschema"),
+            false,
+        ));
+    });
+}
+
+#[test]
+fn test_symcc_check_property_holds_view_box() {
+    SETTINGS.bind(|| {
+        insta::assert_snapshot!(cmd_output(
+            &mut cargo::cargo_bin_cmd!()
+                .current_dir("examples/analyze/view_box")
+                .arg("symcc")
+                .arg("check-property")
+                .arg("policies1.cedar")
+                .arg("property_amelia.cedar")
+                .arg("policies.cedarschema"),
+            false,
+        ));
+    });
+}
+
+#[test]
+fn test_symcc_check_property_violated_view_box() {
+    SETTINGS.bind(|| {
+        insta::assert_snapshot!(cmd_output(
+            &mut cargo::cargo_bin_cmd!()
+                .current_dir("examples/analyze/view_box")
+                .arg("symcc")
+                .arg("check-property")
+                .arg("policies1.cedar")
+                .arg("property_boxes.cedar")
+                .arg("policies.cedarschema"),
+            false,
+        ));
+    });
+}
+
+#[test]
+fn test_symcc_check_implies_export_cex_view_box() {
+    let export_dir =
+        std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("check_implies_export_cex_view_box");
+    // test cases left by an earlier run would also be checked below
+    let _ = std::fs::remove_dir_all(&export_dir);
+    cmd_output(
+        &mut cargo::cargo_bin_cmd!()
+            .current_dir("examples/analyze/view_box")
+            .arg("symcc")
+            .arg("check-implies")
+            .arg("templates.cedar")
+            .arg("sarif.cedar")
+            .arg("policies.cedarschema")
+            .arg("--links")
+            .arg("links.json")
+            .arg("--cex")
+            .arg("--export-cex")
+            .arg(&export_dir),
+        false,
+    );
+    // a test case for each PolicySet and request environment (`Box` and `Thing` resources), whose
+    // template-linked policies are exported as static policies
+    let test_cases: Vec<_> = std::fs::read_dir(&export_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| {
+            let file_name = path.file_name().unwrap().to_string_lossy();
+            file_name.ends_with(".json") && !file_name.ends_with(".entities.json")
+        })
+        .collect();
+    assert_eq!(test_cases.len(), 4, "unexpected test cases: {test_cases:?}");
+    for test_case in test_cases {
+        perform_integration_test_from_json_custom(&test_case, &RustEngine::new());
+    }
+}
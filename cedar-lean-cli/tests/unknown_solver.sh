@@ -0,0 +1,8 @@
+#!/bin/sh
+# A stand-in for CVC5 that gives up on every query
+while IFS= read -r line; do
+    case "$line" in
+        "(check-sat)") echo unknown ;;
+        "(exit)") exit 0 ;;
+    esac
+done
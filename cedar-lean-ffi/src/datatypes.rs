@@ -263,6 +263,35 @@ pub enum ValidationResponse {
     Error(String),
 }
 
+/// Evaluation Response
+#[derive(Debug, Deserialize)]
+pub enum EvaluationResponse {
+    /// The value the expression evaluated to
+    #[serde(rename = "ok")]
+    Ok(Value),
+    /// The expression failed to evaluate
+    #[serde(rename = "error")]
+    Error(EvaluationError),
+}
+
+/// Lean type: Spec.Error
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Error)]
+#[serde(rename_all = "camelCase")]
+pub enum EvaluationError {
+    #[error("entityDoesNotExist")]
+    EntityDoesNotExist,
+    #[error("attrDoesNotExist")]
+    AttrDoesNotExist,
+    #[error("tagDoesNotExist")]
+    TagDoesNotExist,
+    #[error("typeError")]
+    TypeError,
+    #[error("arithBoundsError")]
+    ArithBoundsError,
+    #[error("extensionError")]
+    ExtensionError,
+}
+
 /********************************** SymCC Terms **********************************/
 
 #[derive(Debug, Deserialize)]
@@ -1092,24 +1121,34 @@ pub struct Request {
     pub context: Vec<(String, Value)>,
 }
 
-impl TryFrom<Request> for cedar_policy::Request {
-    type Error = Box<dyn miette::Diagnostic>;
-    fn try_from(req: Request) -> Result<Self, Self::Error> {
-        Ok(Self::new(
-            req.principal.into(),
-            req.action.into(),
-            req.resource.into(),
+impl Request {
+    /// Convert into a `cedar_policy::Request`, validated against `schema` if one is given
+    fn to_cedar_request(
+        self,
+        schema: Option<&cedar_policy::Schema>,
+    ) -> Result<cedar_policy::Request, Box<dyn miette::Diagnostic>> {
+        Ok(cedar_policy::Request::new(
+            self.principal.into(),
+            self.action.into(),
+            self.resource.into(),
             cedar_policy::Context::from_pairs(
-                req.context
+                self.context
                     .into_iter()
                     .map(|(k, v)| Ok((k, v.try_into()?)))
                     .collect::<Result<Vec<_>, Box<dyn miette::Diagnostic>>>()?,
             )?,
-            None,
+            schema,
         )?)
     }
 }
 
+impl TryFrom<Request> for cedar_policy::Request {
+    type Error = Box<dyn miette::Diagnostic>;
+    fn try_from(req: Request) -> Result<Self, Self::Error> {
+        req.to_cedar_request(None)
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct EntityData {
     /// Lean gives us a JSON array of pairs, not a JSON object (map)
@@ -1130,32 +1169,153 @@ pub struct Env {
     pub entities: Vec<(EntityUid, EntityData)>,
 }
 
+impl Env {
+    /// Convert the counterexample into a concrete `cedar_policy::Request` and `cedar_policy::Entities`
+    pub fn to_request_and_entities(
+        self,
+    ) -> Result<(cedar_policy::Request, cedar_policy::Entities), Box<dyn miette::Diagnostic>> {
+        self.to_request_and_entities_with_schema(None)
+    }
+
+    /// Like `to_request_and_entities`, but also validate the request and entities against
+    /// `schema`, if one is given
+    pub fn to_request_and_entities_with_schema(
+        self,
+        schema: Option<&cedar_policy::Schema>,
+    ) -> Result<(cedar_policy::Request, cedar_policy::Entities), Box<dyn miette::Diagnostic>> {
+        let request = self.request.to_cedar_request(schema)?;
+        let entities = cedar_policy::Entities::from_entities(
+            self.entities
+                .into_iter()
+                .map(|(uid, ed)| {
+                    Ok(cedar_policy::Entity::new_with_tags(
+                        uid.into(),
+                        ed.attrs
+                            .into_iter()
+                            .map(|(k, v)| Ok((k, v.try_into()?)))
+                            .collect::<Result<Vec<_>, Box<dyn miette::Diagnostic>>>()?,
+                        ed.ancestors.into_iter().map(|uid| uid.into()),
+                        ed.tags
+                            .into_iter()
+                            .map(|(k, v)| Ok((k, v.try_into()?)))
+                            .collect::<Result<Vec<_>, Box<dyn miette::Diagnostic>>>()?,
+                    )?)
+                })
+                .collect::<Result<Vec<_>, Box<dyn miette::Diagnostic>>>()?,
+            schema,
+        )?;
+        Ok((request, entities))
+    }
+
+    /// Shrink the counterexample by repeatedly dropping entities, ancestor edges, attributes, tags,
+    /// context fields, and record fields or set elements nested within them. Each removal is kept
+    /// only if the result still conforms to `schema` (if one is given) and `is_witness` still holds
+    /// of it, so both should hold of `self`. The result is minimal in that no single further
+    /// removal preserves them.
+    pub fn minimize(
+        self,
+        schema: Option<&cedar_policy::Schema>,
+        mut is_witness: impl FnMut(&Env) -> bool,
+    ) -> Env {
+        let mut env = self;
+        while let Some(smaller) = env
+            .shrinks()
+            .into_iter()
+            .find(|cand| schema.is_none_or(|schema| cand.conforms_to(schema)) && is_witness(cand))
+        {
+            env = smaller;
+        }
+        env
+    }
+
+    /// Whether the request and entities of the counterexample are valid for `schema` (e.g.,
+    /// dropping a required attribute makes them invalid)
+    fn conforms_to(&self, schema: &cedar_policy::Schema) -> bool {
+        self.clone()
+            .to_request_and_entities_with_schema(Some(schema))
+            .is_ok()
+    }
+
+    /// Every `Env` obtained from this one by a single removal
+    fn shrinks(&self) -> Vec<Env> {
+        let mut shrinks = Vec::new();
+        for context in shrink_pairs(&self.request.context) {
+            let mut env = self.clone();
+            env.request.context = context;
+            shrinks.push(env);
+        }
+        for (ind, (_, data)) in self.entities.iter().enumerate() {
+            let mut env = self.clone();
+            env.entities.remove(ind);
+            shrinks.push(env);
+            for ancestor_ind in 0..data.ancestors.len() {
+                let mut env = self.clone();
+                env.entities[ind].1.ancestors.remove(ancestor_ind);
+                shrinks.push(env);
+            }
+            for attrs in shrink_pairs(&data.attrs) {
+                let mut env = self.clone();
+                env.entities[ind].1.attrs = attrs;
+                shrinks.push(env);
+            }
+            for tags in shrink_pairs(&data.tags) {
+                let mut env = self.clone();
+                env.entities[ind].1.tags = tags;
+                shrinks.push(env);
+            }
+        }
+        shrinks
+    }
+}
+
+/// Every list obtained from `pairs` by dropping one pair or shrinking one value
+fn shrink_pairs(pairs: &[(String, Value)]) -> Vec<Vec<(String, Value)>> {
+    let mut shrinks = Vec::new();
+    for (ind, (key, value)) in pairs.iter().enumerate() {
+        let mut dropped = pairs.to_vec();
+        dropped.remove(ind);
+        shrinks.push(dropped);
+        for value in value.shrinks() {
+            let mut shrunk = pairs.to_vec();
+            shrunk[ind] = (key.clone(), value);
+            shrinks.push(shrunk);
+        }
+    }
+    shrinks
+}
+
+impl Value {
+    /// Every `Value` obtained from this one by dropping a single record field or set element, at any depth
+    fn shrinks(&self) -> Vec<Value> {
+        match self {
+            Value::Record { m } => shrink_pairs(m)
+                .into_iter()
+                .map(|m| Value::Record { m })
+                .collect(),
+            Value::Set { s } => {
+                let mut shrinks = Vec::new();
+                for (ind, elem) in s.iter().enumerate() {
+                    let mut dropped = s.clone();
+                    dropped.remove(ind);
+                    shrinks.push(Value::Set { s: dropped });
+                    for elem in elem.shrinks() {
+                        let mut shrunk = s.clone();
+                        shrunk[ind] = elem;
+                        shrinks.push(Value::Set { s: shrunk });
+                    }
+                }
+                shrinks
+            }
+            Value::Prim { .. } | Value::Ext { .. } => Vec::new(),
+        }
+    }
+}
+
 impl TryFrom<Env> for cedar_policy_symcc::Env {
     type Error = Box<dyn miette::Diagnostic>;
     fn try_from(env: Env) -> Result<Self, Self::Error> {
-        Ok(Self {
-            request: env.request.try_into()?,
-            entities: cedar_policy::Entities::from_entities(
-                env.entities
-                    .into_iter()
-                    .map(|(uid, ed)| {
-                        Ok(cedar_policy::Entity::new_with_tags(
-                            uid.into(),
-                            ed.attrs
-                                .into_iter()
-                                .map(|(k, v)| Ok((k, v.try_into()?)))
-                                .collect::<Result<Vec<_>, Box<dyn miette::Diagnostic>>>()?,
-                            ed.ancestors.into_iter().map(|uid| uid.into()),
-                            ed.tags
-                                .into_iter()
-                                .map(|(k, v)| Ok((k, v.try_into()?)))
-                                .collect::<Result<Vec<_>, Box<dyn miette::Diagnostic>>>()?,
-                        )?)
-                    })
-                    .collect::<Result<Vec<_>, Box<dyn miette::Diagnostic>>>()?,
-                None,
-            )?,
-        })
+        let (request, entities) = env.to_request_and_entities()?;
+        Ok(Self { request, entities })
     }
 }
 
@@ -1234,3 +1394,164 @@ mod decimal_conversion {
         let _: RestrictedExpression = Decimal(0).into();
     }
 }
+
+#[cfg(test)]
+mod minimization {
+    use super::{EntityData, EntityUid, Env, Prim, Request, Value};
+    use std::str::FromStr;
+
+    fn uid(s: &str) -> EntityUid {
+        cedar_policy::EntityUid::from_str(s)
+            .expect("should be a valid entity uid")
+            .into()
+    }
+
+    fn int(i: i64) -> Value {
+        Value::Prim { p: Prim::Int(i) }
+    }
+
+    /// `Env` has no `PartialEq`, so counterexamples are compared in their JSON form
+    fn to_json(env: &Env) -> serde_json::Value {
+        serde_json::to_value(env).expect("should serialize")
+    }
+
+    #[test]
+    fn shrinks_nested_values() {
+        let value = Value::Set {
+            s: vec![Value::Record {
+                m: vec![("a".into(), int(1))],
+            }],
+        };
+        let shrinks: Vec<_> = value
+            .shrinks()
+            .iter()
+            .map(|v| serde_json::to_value(v).expect("should serialize"))
+            .collect();
+        let expected: Vec<_> = [
+            Value::Set { s: vec![] },
+            Value::Set {
+                s: vec![Value::Record { m: vec![] }],
+            },
+        ]
+        .iter()
+        .map(|v| serde_json::to_value(v).expect("should serialize"))
+        .collect();
+        assert_eq!(shrinks, expected);
+    }
+
+    #[test]
+    fn drops_everything_irrelevant() {
+        let env = Env {
+            request: Request {
+                principal: uid(r#"User::"alice""#),
+                action: uid(r#"Action::"view""#),
+                resource: uid(r#"Doc::"d""#),
+                context: vec![("c".into(), int(0))],
+            },
+            entities: vec![
+                (
+                    uid(r#"User::"alice""#),
+                    EntityData {
+                        attrs: vec![
+                            (
+                                "r".into(),
+                                Value::Record {
+                                    m: vec![("x".into(), int(1)), ("y".into(), int(2))],
+                                },
+                            ),
+                            ("z".into(), int(3)),
+                        ],
+                        ancestors: vec![uid(r#"Group::"g""#)],
+                        tags: vec![("t".into(), int(4))],
+                    },
+                ),
+                (
+                    uid(r#"Group::"g""#),
+                    EntityData {
+                        attrs: vec![],
+                        ancestors: vec![],
+                        tags: vec![],
+                    },
+                ),
+            ],
+        };
+        // Only `alice.r.y` is relevant
+        let min = env.minimize(None, |env| {
+            env.entities.iter().any(|(_, data)| {
+                data.attrs.iter().any(|(attr, value)| {
+                    attr == "r"
+                        && matches!(value, Value::Record { m } if m.iter().any(|(field, _)| field == "y"))
+                })
+            })
+        });
+        let expected = Env {
+            request: Request {
+                principal: uid(r#"User::"alice""#),
+                action: uid(r#"Action::"view""#),
+                resource: uid(r#"Doc::"d""#),
+                context: vec![],
+            },
+            entities: vec![(
+                uid(r#"User::"alice""#),
+                EntityData {
+                    attrs: vec![(
+                        "r".into(),
+                        Value::Record {
+                            m: vec![("y".into(), int(2))],
+                        },
+                    )],
+                    ancestors: vec![],
+                    tags: vec![],
+                },
+            )],
+        };
+        assert_eq!(to_json(&min), to_json(&expected));
+    }
+
+    #[test]
+    fn keeps_what_the_schema_requires() {
+        let (schema, _) = cedar_policy::Schema::from_cedarschema_str(
+            r#"
+            entity User { age: Long, nick?: Long };
+            entity Doc;
+            action view appliesTo { principal: [User], resource: [Doc], context: { c: Long } };
+            "#,
+        )
+        .expect("should be a valid schema");
+        let env = Env {
+            request: Request {
+                principal: uid(r#"User::"alice""#),
+                action: uid(r#"Action::"view""#),
+                resource: uid(r#"Doc::"d""#),
+                context: vec![("c".into(), int(0))],
+            },
+            entities: vec![(
+                uid(r#"User::"alice""#),
+                EntityData {
+                    attrs: vec![("age".into(), int(1)), ("nick".into(), int(2))],
+                    ancestors: vec![],
+                    tags: vec![],
+                },
+            )],
+        };
+        // Only the presence of `alice` is relevant, but `age` and `c` are required by the schema
+        let min = env.minimize(Some(&schema), |env| !env.entities.is_empty());
+        let expected = Env {
+            request: Request {
+                principal: uid(r#"User::"alice""#),
+                action: uid(r#"Action::"view""#),
+                resource: uid(r#"Doc::"d""#),
+                context: vec![("c".into(), int(0))],
+            },
+            entities: vec![(
+                uid(r#"User::"alice""#),
+                EntityData {
+                    attrs: vec![("age".into(), int(1))],
+                    ancestors: vec![],
+                    tags: vec![],
+                },
+            )],
+        };
+        assert_eq!(to_json(&min), to_json(&expected));
+    }
+}
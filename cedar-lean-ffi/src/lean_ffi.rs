@@ -22,8 +22,8 @@
 mod tpe;
 
 use crate::datatypes::{
-    AuthorizationResponse, AuthorizationResponseInner, Env, ResultDef, Term, TimedDef, TimedResult,
-    TpeResponse, TpeResponseInner, ValidationResponse,
+    AuthorizationResponse, AuthorizationResponseInner, Env, EvaluationResponse, ResultDef, Term,
+    TimedDef, TimedResult, TpeResponse, TpeResponseInner, ValidationResponse,
 };
 use crate::err::FfiError;
 use crate::lean_object::{
@@ -229,6 +229,7 @@ unsafe extern "C" {
     fn levelValidate(req: *mut lean_object) -> *mut lean_object;
     fn printEvaluation(req: *mut lean_object) -> *mut lean_object;
     fn checkEvaluate(req: *mut lean_object) -> *mut lean_object;
+    fn evaluateExpr(req: *mut lean_object) -> *mut lean_object;
     fn validateEntities(req: *mut lean_object) -> *mut lean_object;
     fn validateRequest(req: *mut lean_object) -> *mut lean_object;
 
@@ -573,7 +574,12 @@ macro_rules! checkAsserts_func {
 }
 
 impl CedarLeanFfi {
-    /// WARNING: we can only have one Lean thread
+    /// Initializes the Lean runtime (once per process) and the current thread for Lean.
+    /// The thread is finalized when the returned `CedarLeanFfi` is dropped.
+    ///
+    /// WARNING: every thread calling into Lean needs its own `CedarLeanFfi`, which must be
+    /// dropped on the thread that created it. Lean objects (e.g., a `LeanSchema`) must not
+    /// be shared between threads.
     pub fn new() -> Self {
         START.call_once(|| {
             unsafe {
@@ -809,6 +815,22 @@ impl CedarLeanFfi {
         ()
     );
 
+    checkPolicy_func!(
+        print_check_always_matches_timed,
+        print_check_always_matches,
+        printCheckAlwaysMatches,
+        |x| x,
+        ()
+    );
+
+    checkPolicy_func!(
+        print_check_never_matches_timed,
+        print_check_never_matches,
+        printCheckNeverMatches,
+        |x| x,
+        ()
+    );
+
     checkPolicySet_func!(
         print_check_always_allows_timed,
         print_check_always_allows,
@@ -849,6 +871,30 @@ impl CedarLeanFfi {
         ()
     );
 
+    comparePolicies_func!(
+        print_check_matches_equivalent_timed,
+        print_check_matches_equivalent,
+        printCheckMatchesEquivalent,
+        |x| x,
+        ()
+    );
+
+    comparePolicies_func!(
+        print_check_matches_implies_timed,
+        print_check_matches_implies,
+        printCheckMatchesImplies,
+        |x| x,
+        ()
+    );
+
+    comparePolicies_func!(
+        print_check_matches_disjoint_timed,
+        print_check_matches_disjoint,
+        printCheckMatchesDisjoint,
+        |x| x,
+        ()
+    );
+
     checkAsserts_func!(
         run_check_asserts_timed,
         run_check_asserts,
@@ -970,6 +1016,22 @@ impl CedarLeanFfi {
         String
     );
 
+    checkPolicy_func!(
+        smtlib_of_check_always_matches_timed,
+        smtlib_of_check_always_matches,
+        smtLibOfCheckAlwaysMatches,
+        |x| x,
+        String
+    );
+
+    checkPolicy_func!(
+        smtlib_of_check_never_matches_timed,
+        smtlib_of_check_never_matches,
+        smtLibOfCheckNeverMatches,
+        |x| x,
+        String
+    );
+
     checkPolicySet_func!(
         smtlib_of_check_always_allows_timed,
         smtlib_of_check_always_allows,
@@ -1010,6 +1072,30 @@ impl CedarLeanFfi {
         String
     );
 
+    comparePolicies_func!(
+        smtlib_of_check_matches_equivalent_timed,
+        smtlib_of_check_matches_equivalent,
+        smtLibOfCheckMatchesEquivalent,
+        |x| x,
+        String
+    );
+
+    comparePolicies_func!(
+        smtlib_of_check_matches_implies_timed,
+        smtlib_of_check_matches_implies,
+        smtLibOfCheckMatchesImplies,
+        |x| x,
+        String
+    );
+
+    comparePolicies_func!(
+        smtlib_of_check_matches_disjoint_timed,
+        smtlib_of_check_matches_disjoint,
+        smtLibOfCheckMatchesDisjoint,
+        |x| x,
+        String
+    );
+
     /// Calls the lean backend to determine if the `Request` is allowed
     /// by the `PolicySet` given the provided set of `Entities`
     pub fn is_authorized_timed(
@@ -1114,6 +1200,36 @@ impl CedarLeanFfi {
             .take_result())
     }
 
+    /// Calls the lean backend to evaluate the input Cedar `Expression`, returning the resulting
+    /// `Value` or evaluation error
+    pub fn evaluate_timed(
+        &self,
+        input_expr: &Expression,
+        entities: &Entities,
+        request: &Request,
+    ) -> Result<TimedResult<EvaluationResponse>, FfiError> {
+        let response = unsafe {
+            call_lean_ffi_takes_protobuf(
+                evaluateExpr,
+                &proto::EvaluationRequestChecked::new(input_expr, entities, request),
+            )
+        };
+        match response.as_borrowed().deserialize_into()? {
+            ResultDef::Ok(res) => Ok(TimedResult::from_def(res)),
+            ResultDef::Error(s) => Err(FfiError::LeanBackendError(s)),
+        }
+    }
+    pub fn evaluate(
+        &self,
+        input_expr: &Expression,
+        entities: &Entities,
+        request: &Request,
+    ) -> Result<EvaluationResponse, FfiError> {
+        Ok(self
+            .evaluate_timed(input_expr, entities, request)?
+            .take_result())
+    }
+
     /// Calls the lean backend to validate the `PolicySet` against the provided `Schema`
     pub fn validate_timed(
         &self,
@@ -1310,7 +1426,8 @@ mod test {
     use std::str::FromStr;
 
     use crate::{
-        CedarLeanFfi, TimedResult, ValidationResponse,
+        CedarLeanFfi, EvaluationError, EvaluationResponse, TimedResult, ValidationResponse, Value,
+        datatypes::Prim,
         lean_ffi::{ffiTestExceptErr, ffiTestExceptOk, ffiTestString},
         lean_object::LeanObject,
     };
@@ -1982,6 +2099,32 @@ mod test {
         assert!(!res, "check_evaluate returned wrong result: Expected false");
     }
 
+    #[test]
+    fn test_evaluate() {
+        let input_expr = Expression::from_str("[1 + 2, 4]").expect("Failed to parse expression");
+        let err_expr = Expression::from_str("1 + true").expect("Failed to parse expression");
+        let entities = Entities::empty();
+        let req = request(
+            "Identity::\"Alice\"",
+            "Action::\"view\"",
+            "Thing::\"Thing1\"",
+        );
+
+        let ffi = CedarLeanFfi::new();
+
+        let res = ffi
+            .evaluate(&input_expr, &entities, &req)
+            .expect("Lean call unexpectedly failed for evaluate");
+        assert_matches!(res, EvaluationResponse::Ok(Value::Set { s }) => {
+            assert_matches!(s.as_slice(), [Value::Prim { p: Prim::Int(3) }, Value::Prim { p: Prim::Int(4) }]);
+        });
+
+        let res = ffi
+            .evaluate(&err_expr, &entities, &req)
+            .expect("Lean call unexpectedly failed for evaluate");
+        assert_matches!(res, EvaluationResponse::Error(EvaluationError::TypeError));
+    }
+
     #[test]
     fn test_validate() {
         let always_allows_pset = PolicySet::from_str("permit(principal, action, resource);")
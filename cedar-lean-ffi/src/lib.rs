@@ -20,7 +20,12 @@ mod lean_ffi;
 mod lean_object;
 mod messages;
 
-pub use datatypes::{AuthorizationResponse, TimedResult, ValidationResponse};
+pub use datatypes::{
+    AuthorizationResponse, EvaluationError, EvaluationResponse, TimedResult, ValidationResponse,
+};
+// Partial authorization (TPE) response and residuals
+pub use datatypes::TpeResponse;
+pub use datatypes::tpe::{Residual, TpeResidualPolicy};
 // Term, Env, and associated types
 pub use datatypes::{
     Bitvec, Cidr, Datetime, Decimal, Duration, EntityData, EntityUid, Env, Ext, ExtOp, ExtType,
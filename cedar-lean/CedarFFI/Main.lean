@@ -140,6 +140,17 @@ unsafe def runFfiM {α : Type} [Lean.ToJson α] (m : FfiM α) : String :=
       | _, _ => false
     )
 
+/--
+  `req`: binary protobuf for an `EvaluationRequest`
+
+  returns a string containing JSON, encoding either the value the expression
+  evaluates to (.ok v) or the evaluation error (.error e)
+-/
+@[export evaluateExpr] unsafe def evaluateExprFFI (req : ByteArray) : String :=
+  runFfiM do
+    let v ← (@Proto.Message.interpret? EvaluationRequest) req |>.mapError (s!"failed to parse input: {·}")
+    runAndTime (λ () => evaluate v.expr v.request v.entities)
+
 /--
   `req`: binary protobuf for an `EntityValidationRequest`
 